@@ -0,0 +1,164 @@
+#[macro_export]
+macro_rules! gen_aabb {
+    ($ident:ident, $vec:tt, $typ:ty, $two:literal) => {
+        /// 3D axis aligned box from `start` (minimum corner) to `end` (maximum corner).
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub start: $vec,
+            pub end: $vec,
+        }
+
+        impl $ident {
+            pub const IDENT: Self = Self { start: <$vec>::ZERO, end: <$vec>::ONE };
+
+            pub const fn new(start: $vec, end: $vec) -> Self {
+                return Self { start, end };
+            }
+
+            /// Smallest box containing every point, `None` for an empty slice.
+            pub fn from_points(points: &[$vec]) -> Option<Self> {
+                let (start, end) = $crate::traits::bounding_box(points)?;
+                return Some(Self { start, end });
+            }
+
+            pub fn size(&self) -> $vec {
+                return self.end - self.start;
+            }
+
+            /// Half of the size.
+            pub fn extents(&self) -> $vec {
+                return self.size() / $two;
+            }
+
+            pub fn center(&self) -> $vec {
+                return (self.start + self.end) / $two;
+            }
+
+            pub fn volume(&self) -> $typ {
+                let size = self.size();
+                return size.0 * size.1 * size.2;
+            }
+
+            pub fn surface_area(&self) -> $typ {
+                let size = self.size();
+                return $two * (size.0 * size.1 + size.1 * size.2 + size.2 * size.0);
+            }
+
+            pub fn contains(&self, pos: $vec) -> bool {
+                return
+                    pos.0 >= self.start.0 && pos.0 <= self.end.0 &&
+                    pos.1 >= self.start.1 && pos.1 <= self.end.1 &&
+                    pos.2 >= self.start.2 && pos.2 <= self.end.2;
+            }
+
+            pub fn intersects(&self, other: &Self) -> bool {
+                return
+                    self.start.0 <= other.end.0 && self.end.0 >= other.start.0 &&
+                    self.start.1 <= other.end.1 && self.end.1 >= other.start.1 &&
+                    self.start.2 <= other.end.2 && self.end.2 >= other.start.2;
+            }
+
+            /// Smallest box containing both boxes.
+            pub fn union(&self, other: &Self) -> Self {
+                return Self { start: self.start.min(other.start), end: self.end.max(other.end) };
+            }
+
+            /// Overlapping part of both boxes, `None` if they don't touch.
+            pub fn intersection(&self, other: &Self) -> Option<Self> {
+                if !self.intersects(other) {
+                    return None;
+                }
+                return Some(Self { start: self.start.max(other.start), end: self.end.min(other.end) });
+            }
+
+            pub fn expand(self, fact: $typ) -> Self {
+                return Self { start: self.start - $vec::one(fact), end: self.end + $vec::one(fact) };
+            }
+
+            /// Point of the box closest to `pos`, `pos` itself if it is inside.
+            pub fn closest_point(&self, pos: $vec) -> $vec {
+                return pos.clamp(self.start, self.end);
+            }
+
+            /// The 8 corners, the index bits selecting `end` over `start` for x (bit 0), y (bit 1) and z (bit 2).
+            pub fn corners(&self) -> [$vec; 8] {
+                let (s, e) = (self.start, self.end);
+                return [
+                    $vec(s.0, s.1, s.2),
+                    $vec(e.0, s.1, s.2),
+                    $vec(s.0, e.1, s.2),
+                    $vec(e.0, e.1, s.2),
+                    $vec(s.0, s.1, e.2),
+                    $vec(e.0, s.1, e.2),
+                    $vec(s.0, e.1, e.2),
+                    $vec(e.0, e.1, e.2),
+                ];
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn measurements() {
+        let aabb = Aabbd::new(Vec3d(-1.0, 0.0, 2.0), Vec3d(3.0, 2.0, 3.0));
+        assert_eq!(aabb.size(), Vec3d(4.0, 2.0, 1.0));
+        assert_eq!(aabb.extents(), Vec3d(2.0, 1.0, 0.5));
+        assert_eq!(aabb.center(), Vec3d(1.0, 1.0, 2.5));
+        assert_eq!(aabb.volume(), 8.0);
+        assert_eq!(aabb.surface_area(), 2.0 * (8.0 + 2.0 + 4.0));
+
+        let aabb = Aabbi::new(Vec3i(0, 0, 0), Vec3i(4, 2, 6));
+        assert_eq!(aabb.center(), Vec3i(2, 1, 3));
+        assert_eq!(aabb.volume(), 48);
+    }
+
+    #[test]
+    fn from_points_contains_them() {
+        let points = [Vec3d(1.0, -2.0, 0.5), Vec3d(-3.0, 4.0, 0.0), Vec3d(0.0, 0.0, 7.0)];
+        let aabb = Aabbd::from_points(&points).unwrap();
+        assert_eq!(aabb, Aabbd::new(Vec3d(-3.0, -2.0, 0.0), Vec3d(1.0, 4.0, 7.0)));
+        assert!(points.iter().all(|&p| aabb.contains(p)));
+        assert!(!aabb.contains(Vec3d(1.0 + 1e-9, 0.0, 1.0)));
+        assert_eq!(Aabbd::from_points(&[]), None);
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = Aabbd::new(Vec3d::ZERO, Vec3d::one(2.0));
+        let b = Aabbd::new(Vec3d(1.0, 1.0, -1.0), Vec3d(3.0, 3.0, 1.0));
+        assert_eq!(a.union(&b), Aabbd::new(Vec3d(0.0, 0.0, -1.0), Vec3d(3.0, 3.0, 2.0)));
+        assert_eq!(a.intersection(&b), Some(Aabbd::new(Vec3d(1.0, 1.0, 0.0), Vec3d(2.0, 2.0, 1.0))));
+        assert!(a.intersects(&b) && b.intersects(&a));
+
+        // closed boxes, touching counts
+        let touching = Aabbd::new(Vec3d(2.0, 0.0, 0.0), Vec3d(3.0, 1.0, 1.0));
+        assert!(a.intersects(&touching));
+        assert_eq!(a.intersection(&touching).unwrap().volume(), 0.0);
+
+        let apart = Aabbd::new(Vec3d(2.5, 0.0, 0.0), Vec3d(3.0, 1.0, 1.0));
+        assert!(!a.intersects(&apart));
+        assert_eq!(a.intersection(&apart), None);
+    }
+
+    #[test]
+    fn expand_and_closest_point() {
+        let aabb = Aabbd::new(Vec3d::ZERO, Vec3d::one(2.0)).expand(1.0);
+        assert_eq!(aabb, Aabbd::new(Vec3d::one(-1.0), Vec3d::one(3.0)));
+        assert_eq!(aabb.closest_point(Vec3d(5.0, 1.0, -4.0)), Vec3d(3.0, 1.0, -1.0));
+        assert_eq!(aabb.closest_point(Vec3d(0.5, 1.0, 2.0)), Vec3d(0.5, 1.0, 2.0));
+    }
+
+    #[test]
+    fn corners_follow_the_index_bits() {
+        let aabb = Aabbd::new(Vec3d(-1.0, -2.0, -3.0), Vec3d(1.0, 2.0, 3.0));
+        for (i, corner) in aabb.corners().into_iter().enumerate() {
+            let pick = |bit: usize, start: f64, end: f64| if i & bit != 0 { end } else { start };
+            assert_eq!(corner, Vec3d(pick(1, -1.0, 1.0), pick(2, -2.0, 2.0), pick(4, -3.0, 3.0)));
+        }
+        assert_eq!(Aabbd::from_points(&aabb.corners()), Some(aabb));
+    }
+}
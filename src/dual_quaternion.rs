@@ -0,0 +1,259 @@
+#[macro_export]
+macro_rules! dual_quat_gen {
+    ($ident:ident, $quat:ident, $vec:ident, $mat4:ident, $typ:ty) => {
+        /// Rigid transform (rotation + translation) stored as `real + dual * e`, with `e * e = 0`.
+        /// Composes like quaternions, `a * b` applies `b` first.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $ident {
+            pub real: $quat,
+            pub dual: $quat,
+        }
+
+        impl $ident {
+            pub const IDENT: Self = Self { real: $quat::IDENT, dual: $quat(0.0, 0.0, 0.0, 0.0) };
+
+            pub const fn new(real: $quat, dual: $quat) -> Self {
+                return Self { real, dual };
+            }
+
+            /// Rotates by `rot` and then translates by `pos`, like `tf_matrix` with a scale of one.
+            pub fn from_rot_trans(rot: $quat, pos: $vec) -> Self {
+                let rot = rot.normalized();
+                return Self { real: rot, dual: $quat::from(pos) * rot * 0.5 };
+            }
+
+            pub fn from_rot(rot: $quat) -> Self {
+                return Self { real: rot.normalized(), dual: $quat(0.0, 0.0, 0.0, 0.0) };
+            }
+
+            pub fn from_trans(pos: $vec) -> Self {
+                return Self { real: $quat::IDENT, dual: $quat::from(pos * 0.5) };
+            }
+
+            pub fn rotation(&self) -> $quat {
+                return self.real;
+            }
+
+            pub fn translation(&self) -> $vec {
+                return (self.dual * self.real.conjugate()).vector() * 2.0;
+            }
+
+            /// Quaternion conjugate of both parts, the inverse of a unit dual quaternion.
+            pub fn conjugate(&self) -> Self {
+                return Self { real: self.real.conjugate(), dual: self.dual.conjugate() };
+            }
+
+            /// Scales back to unit length and removes the drift that breaks the `real . dual == 0` constraint.
+            pub fn normalized(self) -> Self {
+                let inv_norm = 1.0 / self.real.norm();
+                let real = self.real * inv_norm;
+                let dual = self.dual * inv_norm;
+                return Self { real, dual: dual - real * real.dot(dual) };
+            }
+
+            pub fn inverted(self) -> Self {
+                return self.normalized().conjugate();
+            }
+
+            pub fn transform_point(&self, point: $vec) -> $vec {
+                return point.rotate(self.real) + self.translation();
+            }
+
+            pub fn transform_vector(&self, vec: $vec) -> $vec {
+                return vec.rotate(self.real);
+            }
+
+            /// Raises a unit dual quaternion to a real power, scaling both the angle and the
+            /// distance along its screw axis by `t`.
+            pub fn powf(self, t: $typ) -> Self {
+                let real = if self.real.0 < 0.0 { -self.real } else { self.real };
+                let dual = if self.real.0 < 0.0 { -self.dual } else { self.dual };
+                let pos = (dual * real.conjugate()).vector() * 2.0;
+
+                let sin_half = real.vector().magnitude();
+                if sin_half < <$typ>::EPSILON.sqrt() {
+                    // no rotation, only the translation gets scaled
+                    return Self::from_rot_trans(real.powf(t), pos * t);
+                }
+
+                let angle = 2.0 * sin_half.atan2(real.0);
+                let axis = real.vector() / sin_half;
+                let pitch = pos.dot(axis);
+                let moment = (pos.cross(axis) + (pos - axis * pitch) * (real.0 / sin_half)) * 0.5;
+
+                let (sin, cos) = (angle * t * 0.5).sin_cos();
+                let pitch = pitch * t;
+                return Self {
+                    real: $quat::from_split(cos, axis * sin),
+                    dual: $quat::from_split(-pitch * 0.5 * sin, moment * sin + axis * (pitch * 0.5 * cos)),
+                };
+            }
+
+            /// Screw linear interpolation, moving along the shortest screw motion between both transforms
+            /// at constant speed.
+            pub fn sclerp(self, other: Self, t: $typ) -> Self {
+                let other = if self.real.dot(other.real) < 0.0 { -other } else { other };
+                return (self * (self.inverted() * other).powf(t)).normalized();
+            }
+
+            /// Dual quaternion linear blending of `(transform, weight)` pairs, the usual skinning blend.
+            /// Returns `IDENT` for an empty slice.
+            pub fn dlb(samples: &[(Self, $typ)]) -> Self {
+                let Some(&(first, _)) = samples.first() else {
+                    return Self::IDENT;
+                };
+
+                let mut sum = Self { real: $quat(0.0, 0.0, 0.0, 0.0), dual: $quat(0.0, 0.0, 0.0, 0.0) };
+                for &(dq, weight) in samples {
+                    let weight = if dq.real.dot(first.real) < 0.0 { -weight } else { weight };
+                    sum.real += dq.real * weight;
+                    sum.dual += dq.dual * weight;
+                }
+
+                if sum.real.dot(sum.real) == 0.0 {
+                    return Self::IDENT;
+                }
+                return sum.normalized();
+            }
+
+            /// Builds the same matrix as `tf_matrix(translation, rotation, ONE)`.
+            pub fn to_mat4(&self) -> $mat4 {
+                return $mat4::tf_matrix(self.translation(), self.real.normalized(), $vec::ONE);
+            }
+
+            /// Extracts the rotation and translation of a `tf_matrix`, any scale is discarded.
+            pub fn from_mat4(mat: &$mat4) -> Self {
+                let pos = $vec(mat[(0, 3)], mat[(1, 3)], mat[(2, 3)]);
+                return Self::from_rot_trans($quat::from_rotation_matrix(mat), pos);
+            }
+        }
+
+        impl std::ops::Mul for $ident {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                return Self {
+                    real: self.real * rhs.real,
+                    dual: self.real * rhs.dual + self.dual * rhs.real,
+                };
+            }
+        }
+
+        impl std::ops::MulAssign for $ident {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl std::ops::Neg for $ident {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                return Self { real: -self.real, dual: -self.dual };
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::{assert_vec_eq, mat4_point};
+
+    fn assert_same_transform(a: DualQuatd, b: DualQuatd) {
+        for point in [Vec3d::ZERO, Vec3d::RIGHT, Vec3d::UP, Vec3d(0.5, -2.0, 3.0)] {
+            assert_vec_eq(a.transform_point(point), b.transform_point(point));
+        }
+    }
+
+    fn transforms() -> Vec<DualQuatd> {
+        return vec![
+            DualQuatd::IDENT,
+            DualQuatd::from_trans(Vec3d(1.0, -2.0, 3.0)),
+            DualQuatd::from_rot(Quatd::rotator(Vec3d(0.0, 1.2, 0.0))),
+            DualQuatd::from_rot_trans(Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d(4.0, 0.5, -1.0)),
+            DualQuatd::from_rot_trans(Quatd::rotator(Vec3d(-2.0, 0.5, 0.1)), Vec3d(-3.0, 2.0, 0.25)),
+        ];
+    }
+
+    #[test]
+    fn transform_point_matches_to_mat4() {
+        for dq in transforms() {
+            let mat = dq.to_mat4();
+            for point in [Vec3d::ZERO, Vec3d(0.5, -2.0, 3.0)] {
+                assert_vec_eq(dq.transform_point(point), mat4_point(&mat, point));
+            }
+            assert_vec_eq(dq.transform_vector(Vec3d::UP), mat4_point(&mat, Vec3d::UP) - mat4_point(&mat, Vec3d::ZERO));
+        }
+    }
+
+    #[test]
+    fn from_rot_trans_rotates_then_translates() {
+        let rot = Quatd::rotator(Vec3d(0.0, 0.0, std::f64::consts::FRAC_PI_2));
+        let dq = DualQuatd::from_rot_trans(rot, Vec3d(10.0, 0.0, 0.0));
+        assert_vec_eq(dq.transform_point(Vec3d::RIGHT), Vec3d(10.0, 1.0, 0.0));
+        assert_vec_eq(dq.translation(), Vec3d(10.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn mat4_round_trip() {
+        for dq in transforms() {
+            assert_same_transform(DualQuatd::from_mat4(&dq.to_mat4()), dq);
+        }
+
+        // scale is dropped
+        let rot = Quatd::rotator(Vec3d(0.3, -1.2, 0.7));
+        let mat = Mat4d::tf_matrix(Vec3d(1.0, 2.0, 3.0), rot, Vec3d(2.0, 0.5, 3.0));
+        assert_same_transform(DualQuatd::from_mat4(&mat), DualQuatd::from_rot_trans(rot, Vec3d(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn composition_and_inverse() {
+        let all = transforms();
+        for &a in &all {
+            assert_same_transform(a * a.inverted(), DualQuatd::IDENT);
+            for &b in &all {
+                let point = Vec3d(0.5, -2.0, 3.0);
+                assert_vec_eq((a * b).transform_point(point), a.transform_point(b.transform_point(point)));
+            }
+        }
+    }
+
+    #[test]
+    fn sclerp_endpoints_and_midpoint() {
+        let all = transforms();
+        for &a in &all {
+            for &b in &all {
+                assert_same_transform(a.sclerp(b, 0.0), a);
+                assert_same_transform(a.sclerp(b, 1.0), b);
+                assert_same_transform(a.sclerp(-b, 1.0), b);
+
+                let half = a.sclerp(b, 0.5);
+                let step = a.inverted() * half;
+                assert_same_transform(a * step * step, b);
+            }
+        }
+
+        // pure translations move in a straight line
+        let a = DualQuatd::from_trans(Vec3d(0.0, 0.0, 0.0));
+        let b = DualQuatd::from_trans(Vec3d(4.0, -2.0, 0.0));
+        assert_vec_eq(a.sclerp(b, 0.25).translation(), Vec3d(1.0, -0.5, 0.0));
+    }
+
+    #[test]
+    fn dlb_blends() {
+        assert_eq!(DualQuatd::dlb(&[]), DualQuatd::IDENT);
+
+        let a = DualQuatd::from_rot_trans(Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d(4.0, 0.5, -1.0));
+        assert_same_transform(DualQuatd::dlb(&[(a, 0.3)]), a);
+        assert_same_transform(DualQuatd::dlb(&[(a, 1.0), (-a, 1.0)]), a);
+
+        let b = DualQuatd::from_trans(Vec3d(2.0, 0.0, 0.0));
+        let c = DualQuatd::from_trans(Vec3d(0.0, 2.0, 0.0));
+        assert_vec_eq(DualQuatd::dlb(&[(b, 1.0), (c, 1.0)]).translation(), Vec3d(1.0, 1.0, 0.0));
+
+        let blended = DualQuatd::dlb(&[(a, 1.0), (b, 2.0)]);
+        assert!((blended.real.norm() - 1.0).abs() < 1e-9);
+        assert!(blended.real.dot(blended.dual).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,194 @@
+/// Result of a culling test against a volume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Containment {
+    Inside,
+    Outside,
+    Intersecting,
+}
+
+#[macro_export]
+macro_rules! gen_frustum {
+    ($ident:ident, $plane:ident, $vec3:ident, $quat:ident, $mat4:ident, $aabb:ident, $typ:ty) => {
+        /// View frustum as six normalized planes with the normals pointing inwards, in the order left, right,
+        /// bottom, top, near, far.
+        ///
+        /// The culling tests are conservative: shapes near the frustum corners can be reported as
+        /// intersecting while being outside.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub planes: [$plane; 6],
+        }
+
+        impl $ident {
+            /// Extracts the planes of a `proj * view` matrix (Gribb–Hartmann) for a `[0, 1]` depth range, so
+            /// it works with every projection of `impl_tf4x4`, GL or Vulkan. With the Vulkan projections
+            /// bottom and top are swapped, with reverse-Z near and far are. The far plane of an infinite
+            /// projection has no normal and an infinite distance, so it contains every point.
+            pub fn from_matrix(mat: &$mat4) -> Self {
+                let rows = [mat.row(0), mat.row(1), mat.row(2), mat.row(3)];
+                let plane = |row: <$mat4 as $crate::traits::Matrix>::Vector| -> $plane {
+                    let plane = $plane::new(row.xyz(), row.3).normalized();
+                    if plane.normal == $vec3::ZERO {
+                        // far plane at infinity
+                        return $plane::new($vec3::ZERO, <$typ>::INFINITY);
+                    }
+                    return plane;
+                };
+
+                return Self { planes: [
+                    plane(rows[3] + rows[0]),
+                    plane(rows[3] - rows[0]),
+                    plane(rows[3] + rows[1]),
+                    plane(rows[3] - rows[1]),
+                    plane(rows[2]),
+                    plane(rows[3] - rows[2]),
+                ]};
+            }
+
+            pub fn contains_point(&self, point: $vec3) -> bool {
+                return self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0);
+            }
+
+            pub fn test_sphere(&self, center: $vec3, radius: $typ) -> $crate::frustum::Containment {
+                return self.test(|plane| (plane.signed_distance(center), radius));
+            }
+
+            pub fn test_aabb(&self, aabb: &$aabb) -> $crate::frustum::Containment {
+                let center = aabb.center();
+                let extents = aabb.extents();
+                return self.test(|plane| {
+                    let n = plane.normal;
+                    let radius = (n.0 * extents.0).abs() + (n.1 * extents.1).abs() + (n.2 * extents.2).abs();
+                    return (plane.signed_distance(center), radius);
+                });
+            }
+
+            /// Tests the box of the given half size along its axes, rotated by `rotation` around its center.
+            pub fn test_obb(&self, center: $vec3, half_extents: $vec3, rotation: $quat) -> $crate::frustum::Containment {
+                let axes = [
+                    $vec3::RIGHT.rotate(rotation) * half_extents.0,
+                    $vec3::UP.rotate(rotation) * half_extents.1,
+                    $vec3::FORW.rotate(rotation) * half_extents.2,
+                ];
+                return self.test(|plane| {
+                    let radius = axes.iter().map(|axis| plane.normal.dot(*axis).abs()).sum();
+                    return (plane.signed_distance(center), radius);
+                });
+            }
+
+            // `dist_radius` returns the signed distance of the shape's center to the plane and its extent
+            // along the plane normal
+            fn test(&self, dist_radius: impl Fn(&$plane) -> ($typ, $typ)) -> $crate::frustum::Containment {
+                let mut res = $crate::frustum::Containment::Inside;
+                for plane in &self.planes {
+                    let (dist, radius) = dist_radius(plane);
+                    if dist < -radius {
+                        return $crate::frustum::Containment::Outside;
+                    }
+                    if dist < radius {
+                        res = $crate::frustum::Containment::Intersecting;
+                    }
+                }
+                return res;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    const ASPECT: f64 = 1.5;
+    const FOV: f64 = 1.2;
+    const NEAR: f64 = 0.5;
+    const FAR: f64 = 50.0;
+
+    // every projection, with a flag telling if the far plane is at infinity
+    fn projections() -> Vec<(Mat4d, bool)> {
+        return vec![
+            (Mat4d::proj_matrix(ASPECT, FOV, NEAR, FAR), false),
+            (Mat4d::proj_matrix_vk(ASPECT, FOV, NEAR, FAR), false),
+            (Mat4d::proj_matrix_reverse_z(ASPECT, FOV, NEAR, FAR), false),
+            (Mat4d::proj_matrix_reverse_z_vk(ASPECT, FOV, NEAR, FAR), false),
+            (Mat4d::proj_matrix_infinite(ASPECT, FOV, NEAR), true),
+            (Mat4d::proj_matrix_infinite_vk(ASPECT, FOV, NEAR), true),
+            (Mat4d::proj_matrix_infinite_reverse_z(ASPECT, FOV, NEAR), true),
+            (Mat4d::proj_matrix_infinite_reverse_z_vk(ASPECT, FOV, NEAR), true),
+        ];
+    }
+
+    #[test]
+    fn planes_are_normalized() {
+        for (proj, infinite) in projections() {
+            let frustum = Frustumd::from_matrix(&proj);
+            for plane in frustum.planes {
+                if plane.normal == Vec3d::ZERO {
+                    assert!(infinite);
+                    assert_eq!(plane.dist, f64::INFINITY);
+                } else {
+                    assert!((plane.normal.magnitude() - 1.0).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn contains_point_for_every_projection() {
+        for (proj, infinite) in projections() {
+            let frustum = Frustumd::from_matrix(&proj);
+            assert!(frustum.contains_point(Vec3d(0.0, 0.0, 10.0)));
+            assert!(frustum.contains_point(Vec3d(9.0, -6.0, 10.0)));
+            assert!(!frustum.contains_point(Vec3d(0.0, 0.0, -1.0)));
+            assert!(!frustum.contains_point(Vec3d(0.0, 0.0, 0.25)));
+            assert!(!frustum.contains_point(Vec3d(11.0, 0.0, 10.0)));
+            assert!(!frustum.contains_point(Vec3d(0.0, 7.5, 10.0)));
+            assert_eq!(frustum.contains_point(Vec3d(0.0, 0.0, 60.0)), infinite);
+        }
+    }
+
+    #[test]
+    fn spheres_and_boxes_for_every_projection() {
+        for (proj, infinite) in projections() {
+            let frustum = Frustumd::from_matrix(&proj);
+            let beyond_far = if infinite { Containment::Inside } else { Containment::Outside };
+            let across_far = if infinite { Containment::Inside } else { Containment::Intersecting };
+
+            assert_eq!(frustum.test_sphere(Vec3d(0.0, 0.0, 10.0), 1.0), Containment::Inside);
+            assert_eq!(frustum.test_sphere(Vec3d(0.0, 0.0, 0.0), 1.0), Containment::Intersecting);
+            assert_eq!(frustum.test_sphere(Vec3d(0.0, 0.0, -5.0), 1.0), Containment::Outside);
+            assert_eq!(frustum.test_sphere(Vec3d(0.0, 0.0, FAR), 1.0), across_far);
+            assert_eq!(frustum.test_sphere(Vec3d(0.0, 0.0, 60.0), 1.0), beyond_far);
+
+            let aabb = |center: Vec3d, half: f64| Aabbd::new(center - Vec3d::one(half), center + Vec3d::one(half));
+            assert_eq!(frustum.test_aabb(&aabb(Vec3d(0.0, 0.0, 10.0), 1.0)), Containment::Inside);
+            assert_eq!(frustum.test_aabb(&aabb(Vec3d(10.0, 0.0, 10.0), 1.0)), Containment::Intersecting);
+            assert_eq!(frustum.test_aabb(&aabb(Vec3d(0.0, 20.0, 10.0), 1.0)), Containment::Outside);
+            assert_eq!(frustum.test_aabb(&aabb(Vec3d(0.0, 0.0, 60.0), 1.0)), beyond_far);
+        }
+    }
+
+    #[test]
+    fn obb_uses_the_rotation() {
+        for (proj, _) in projections() {
+            let frustum = Frustumd::from_matrix(&proj);
+            let center = Vec3d(11.5, 0.0, 10.0);
+            let half_extents = Vec3d(2.0, 0.1, 0.1);
+
+            // long along x it reaches into the frustum, turned along y it stays outside
+            assert_eq!(frustum.test_obb(center, half_extents, Quatd::IDENT), Containment::Intersecting);
+            let turned = Quatd::rotator(Vec3d(0.0, 0.0, std::f64::consts::FRAC_PI_2));
+            assert_eq!(frustum.test_obb(center, half_extents, turned), Containment::Outside);
+            assert_eq!(frustum.test_obb(Vec3d(0.0, 0.0, 10.0), half_extents, turned), Containment::Inside);
+        }
+    }
+
+    #[test]
+    fn view_projection_frustum_follows_the_camera() {
+        let view = Mat4d::look_at_lh(Vec3d(10.0, 0.0, 0.0), Vec3d(10.0, 0.0, -10.0), Vec3d::UP);
+        let frustum = Frustumd::from_matrix(&(&Mat4d::proj_matrix(ASPECT, FOV, NEAR, FAR) * &view));
+        assert!(frustum.contains_point(Vec3d(10.0, 0.0, -10.0)));
+        assert!(!frustum.contains_point(Vec3d(10.0, 0.0, 10.0)));
+        assert_eq!(frustum.test_sphere(Vec3d(0.0, 0.0, 0.0), 1.0), Containment::Outside);
+    }
+}
@@ -0,0 +1,400 @@
+use crate::traits::Matrix;
+
+/// Local transform stored in a [`Hierarchy`], implemented by the types generated by
+/// `gen_transform2d` and `gen_transform3d`.
+pub trait HierarchyTransform: Copy {
+    type Matrix: Matrix;
+
+    fn to_matrix(&self) -> Self::Matrix;
+
+    /// Recovers the transform from a matrix, used when reparenting keeps the world transform.
+    /// Returns `None` if the matrix can't be represented, e.g. because it has shear.
+    fn from_matrix(mat: &Self::Matrix) -> Option<Self>;
+}
+
+/// Why `set_parent` or `reparent_keep_world` refused to move a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HierarchyError {
+    /// The new parent is the node itself or one of its descendants.
+    Cycle,
+    /// The new parent's world matrix can't be inverted.
+    SingularParent,
+    /// The new local matrix can't be stored in the transform type, e.g. because it has shear.
+    Unrepresentable,
+}
+
+impl std::fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle => write!(f, "the new parent is the node or one of its descendants"),
+            Self::SingularParent => write!(f, "the new parent's world matrix can't be inverted"),
+            Self::Unrepresentable => write!(f, "the new local matrix can't be stored in the transform type"),
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError {}
+
+/// Parent-child transform tree stored in flat arrays, nodes are referred to by the index returned by `add`.
+///
+/// Changing a local transform or a parent only marks the node as dirty, `update` then recomputes the
+/// world matrices of the dirty nodes and their descendants, parents before children.
+#[derive(Debug, Clone)]
+pub struct Hierarchy<T: HierarchyTransform> {
+    local: Vec<T>,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    world: Vec<T::Matrix>,
+    dirty: Vec<bool>,
+
+    // every node comes after its parent
+    order: Vec<usize>,
+    order_dirty: bool,
+}
+
+impl<T: HierarchyTransform> Hierarchy<T> {
+    pub fn new() -> Self {
+        return Self {
+            local: Vec::new(),
+            parent: Vec::new(),
+            children: Vec::new(),
+            world: Vec::new(),
+            dirty: Vec::new(),
+            order: Vec::new(),
+            order_dirty: false,
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.local.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.local.is_empty();
+    }
+
+    /// Adds a node and returns its index.
+    pub fn add(&mut self, local: T, parent: Option<usize>) -> usize {
+        self.assert_parent(parent);
+
+        let index = self.len();
+        self.local.push(local);
+        self.parent.push(parent);
+        self.children.push(Vec::new());
+        if let Some(parent) = parent {
+            self.children[parent].push(index);
+        }
+        self.world.push(T::Matrix::IDENTITY);
+        self.dirty.push(true);
+
+        // the parent always exists already, so appending keeps the order valid
+        self.order.push(index);
+        return index;
+    }
+
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        return self.parent[node];
+    }
+
+    /// Direct children of the node, in the order they were attached.
+    pub fn children(&self, node: usize) -> &[usize] {
+        return &self.children[node];
+    }
+
+    pub fn local(&self, node: usize) -> &T {
+        return &self.local[node];
+    }
+
+    pub fn set_local(&mut self, node: usize, local: T) {
+        self.local[node] = local;
+        self.dirty[node] = true;
+    }
+
+    /// Gives mutable access to the local transform, marking the node as dirty.
+    pub fn local_mut(&mut self, node: usize) -> &mut T {
+        self.dirty[node] = true;
+        return &mut self.local[node];
+    }
+
+    /// World matrix of the node as of the last `update`.
+    pub fn world(&self, node: usize) -> &T::Matrix {
+        return &self.world[node];
+    }
+
+    pub fn is_dirty(&self, node: usize) -> bool {
+        return self.dirty[node];
+    }
+
+    /// Moves the node under `parent` (or to the root with `None`), keeping its local transform, so it
+    /// moves along with the new parent. Fails if it would create a cycle.
+    pub fn set_parent(&mut self, node: usize, parent: Option<usize>) -> Result<(), HierarchyError> {
+        self.assert_parent(parent);
+        if self.is_ancestor_or_self(node, parent) {
+            return Err(HierarchyError::Cycle);
+        }
+
+        self.move_node(node, parent);
+        return Ok(());
+    }
+
+    /// Moves the node under `parent` (or to the root with `None`), adjusting its local transform so
+    /// its world transform stays the same. Fails if it would create a cycle, the new parent's world
+    /// matrix can't be inverted or the new local matrix can't be stored in a `T`, which happens when
+    /// the parent has a non-uniform scale and the node is rotated relative to it. Nothing is changed
+    /// on failure.
+    pub fn reparent_keep_world(&mut self, node: usize, parent: Option<usize>) -> Result<(), HierarchyError> {
+        self.assert_parent(parent);
+        if self.is_ancestor_or_self(node, parent) {
+            return Err(HierarchyError::Cycle);
+        }
+
+        let world = self.compute_world(node);
+        let local = match parent {
+            Some(parent) => self.compute_world(parent).inverse().ok_or(HierarchyError::SingularParent)?.mul_mat(&world),
+            None => world,
+        };
+
+        self.local[node] = T::from_matrix(&local).ok_or(HierarchyError::Unrepresentable)?;
+        self.move_node(node, parent);
+        return Ok(());
+    }
+
+    /// Recomputes the world matrices of every dirty node and their descendants.
+    pub fn update(&mut self) {
+        if self.order_dirty {
+            self.rebuild_order();
+        }
+
+        for i in 0..self.order.len() {
+            let node = self.order[i];
+            let parent_changed = self.parent[node].is_some_and(|parent| self.dirty[parent]);
+            if !self.dirty[node] && !parent_changed {
+                continue;
+            }
+
+            let local = self.local[node].to_matrix();
+            self.world[node] = match self.parent[node] {
+                Some(parent) => self.world[parent].mul_mat(&local),
+                None => local,
+            };
+            // stays set until the end of the pass so the children see it
+            self.dirty[node] = true;
+        }
+
+        self.dirty.fill(false);
+    }
+
+    fn move_node(&mut self, node: usize, parent: Option<usize>) {
+        if let Some(old) = self.parent[node] {
+            self.children[old].retain(|&child| child != node);
+        }
+        if let Some(parent) = parent {
+            self.children[parent].push(node);
+        }
+
+        self.parent[node] = parent;
+        self.dirty[node] = true;
+        self.order_dirty = true;
+    }
+
+    // computes the world matrix by walking up the parents, ignoring the cache
+    fn compute_world(&self, node: usize) -> T::Matrix {
+        let mut res = self.local[node].to_matrix();
+        let mut current = self.parent[node];
+        while let Some(parent) = current {
+            res = self.local[parent].to_matrix().mul_mat(&res);
+            current = self.parent[parent];
+        }
+        return res;
+    }
+
+    fn assert_parent(&self, parent: Option<usize>) {
+        if let Some(parent) = parent {
+            assert!(parent < self.len(), "Parent index out of bounds. (Index was {}; Length was {})", parent, self.len());
+        }
+    }
+
+    fn is_ancestor_or_self(&self, node: usize, mut other: Option<usize>) -> bool {
+        while let Some(current) = other {
+            if current == node {
+                return true;
+            }
+            other = self.parent[current];
+        }
+        return false;
+    }
+
+    fn rebuild_order(&mut self) {
+        let mut stack = (0..self.len()).filter(|&node| self.parent[node].is_none()).collect::<Vec<_>>();
+
+        self.order.clear();
+        while let Some(node) = stack.pop() {
+            self.order.push(node);
+            stack.extend(self.children[node].iter().rev());
+        }
+
+        self.order_dirty = false;
+    }
+}
+
+impl<T: HierarchyTransform> Default for Hierarchy<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::{assert_mat_eq, assert_vec_eq};
+
+    fn world_pos(hierarchy: &Hierarchy3Dd, node: usize) -> Vec3d {
+        let world = hierarchy.world(node);
+        return Vec3d(world[(0, 3)], world[(1, 3)], world[(2, 3)]);
+    }
+
+    fn translation(pos: Vec3d) -> Transform3Dd {
+        return Transform3Dd::new(pos, Quatd::IDENT, Vec3d::ONE);
+    }
+
+    #[test]
+    fn update_propagates_to_children() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let root = hierarchy.add(translation(Vec3d(1.0, 0.0, 0.0)), None);
+        let child = hierarchy.add(translation(Vec3d(0.0, 2.0, 0.0)), Some(root));
+        let grandchild = hierarchy.add(translation(Vec3d(0.0, 0.0, 3.0)), Some(child));
+        assert!(hierarchy.is_dirty(grandchild));
+
+        hierarchy.update();
+        assert!(!hierarchy.is_dirty(root) && !hierarchy.is_dirty(grandchild));
+        assert_vec_eq(world_pos(&hierarchy, grandchild), Vec3d(1.0, 2.0, 3.0));
+
+        // only the root changes, the descendants still follow
+        hierarchy.set_local(root, translation(Vec3d(-1.0, 0.0, 0.0)));
+        hierarchy.update();
+        assert_vec_eq(world_pos(&hierarchy, grandchild), Vec3d(-1.0, 2.0, 3.0));
+
+        hierarchy.local_mut(child).rotation = Quatd::rotator(Vec3d(0.0, std::f64::consts::FRAC_PI_2, 0.0));
+        hierarchy.update();
+        assert_vec_eq(world_pos(&hierarchy, grandchild), Vec3d(2.0, 2.0, 0.0));
+
+        assert_eq!(hierarchy.children(root), &[child]);
+        assert_eq!(hierarchy.parent(grandchild), Some(child));
+    }
+
+    #[test]
+    fn set_parent_keeps_local_and_rejects_cycles() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let a = hierarchy.add(translation(Vec3d(1.0, 0.0, 0.0)), None);
+        let b = hierarchy.add(translation(Vec3d(0.0, 1.0, 0.0)), Some(a));
+        let c = hierarchy.add(translation(Vec3d(0.0, 0.0, 1.0)), None);
+
+        assert_eq!(hierarchy.set_parent(a, Some(b)), Err(HierarchyError::Cycle));
+        assert_eq!(hierarchy.set_parent(a, Some(a)), Err(HierarchyError::Cycle));
+        assert_eq!(hierarchy.parent(a), None);
+
+        // parenting to a later node needs the update order to be rebuilt
+        assert_eq!(hierarchy.set_parent(a, Some(c)), Ok(()));
+        assert_eq!(hierarchy.children(c), &[a]);
+        hierarchy.update();
+        assert_vec_eq(world_pos(&hierarchy, b), Vec3d(1.0, 1.0, 1.0));
+
+        assert_eq!(hierarchy.set_parent(a, None), Ok(()));
+        assert!(hierarchy.children(c).is_empty());
+        assert_eq!(hierarchy.children(a), &[b]);
+        hierarchy.update();
+        assert_vec_eq(world_pos(&hierarchy, b), Vec3d(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn reparent_keep_world_keeps_the_world_matrix() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let parent = hierarchy.add(
+            Transform3Dd::new(Vec3d(1.0, 2.0, 3.0), Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d::one(2.0)),
+            None,
+        );
+        let node = hierarchy.add(
+            Transform3Dd::new(Vec3d(-2.0, 0.5, 4.0), Quatd::rotator(Vec3d(0.0, 0.0, 1.0)), Vec3d(1.0, 3.0, 0.5)),
+            None,
+        );
+        hierarchy.update();
+        let before = hierarchy.world(node).clone();
+
+        assert_eq!(hierarchy.reparent_keep_world(node, Some(parent)), Ok(()));
+        assert_eq!(hierarchy.parent(node), Some(parent));
+        hierarchy.update();
+        assert_mat_eq(hierarchy.world(node), &before);
+
+        assert_eq!(hierarchy.reparent_keep_world(node, None), Ok(()));
+        hierarchy.update();
+        assert_mat_eq(hierarchy.world(node), &before);
+
+        assert_eq!(hierarchy.reparent_keep_world(parent, Some(parent)), Err(HierarchyError::Cycle));
+    }
+
+    #[test]
+    fn reparent_keep_world_fails_on_shear() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let parent = hierarchy.add(Transform3Dd::new(Vec3d::ZERO, Quatd::IDENT, Vec3d(1.0, 4.0, 0.3)), None);
+        let local = Transform3Dd::new(Vec3d(5.0, 1.0, -2.0), Quatd::rotator(Vec3d(0.4, 0.0, 0.8)), Vec3d::ONE);
+        let node = hierarchy.add(local, None);
+
+        // the rotated node under a non-uniformly scaled parent would need shear
+        assert_eq!(hierarchy.reparent_keep_world(node, Some(parent)), Err(HierarchyError::Unrepresentable));
+        assert_eq!(hierarchy.parent(node), None);
+        assert!(hierarchy.children(parent).is_empty());
+        assert_eq!(*hierarchy.local(node), local);
+
+        hierarchy.update();
+        assert_vec_eq(world_pos(&hierarchy, node), Vec3d(5.0, 1.0, -2.0));
+    }
+
+    #[test]
+    fn reparent_keep_world_fails_on_a_singular_parent() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let parent = hierarchy.add(Transform3Dd::new(Vec3d::ZERO, Quatd::IDENT, Vec3d(1.0, 0.0, 1.0)), None);
+        let node = hierarchy.add(translation(Vec3d(1.0, 2.0, 3.0)), None);
+
+        assert_eq!(hierarchy.reparent_keep_world(node, Some(parent)), Err(HierarchyError::SingularParent));
+        assert_eq!(hierarchy.parent(node), None);
+
+        // keeping the local transform doesn't need the inverse
+        assert_eq!(hierarchy.set_parent(node, Some(parent)), Ok(()));
+    }
+
+    #[test]
+    fn reparent_keep_world_2d() {
+        let mut hierarchy = Hierarchy2Dd::new();
+        let parent = hierarchy.add(Transform2Dd::new(Vec2d(3.0, -1.0), 0.5, Vec2d::one(2.0)), None);
+        let node = hierarchy.add(Transform2Dd::new(Vec2d(1.0, 1.0), -1.0, Vec2d(1.0, 0.5)), None);
+        hierarchy.update();
+        let before = hierarchy.world(node).clone();
+
+        assert_eq!(hierarchy.reparent_keep_world(node, Some(parent)), Ok(()));
+        hierarchy.update();
+        assert_mat_eq(hierarchy.world(node), &before);
+    }
+
+    #[test]
+    #[should_panic(expected = "Parent index out of bounds")]
+    fn add_checks_the_parent() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        hierarchy.add(Transform3Dd::IDENT, Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Parent index out of bounds")]
+    fn set_parent_checks_the_parent() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let node = hierarchy.add(Transform3Dd::IDENT, None);
+        let _ = hierarchy.set_parent(node, Some(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Parent index out of bounds")]
+    fn reparent_keep_world_checks_the_parent() {
+        let mut hierarchy = Hierarchy3Dd::new();
+        let node = hierarchy.add(Transform3Dd::IDENT, None);
+        let _ = hierarchy.reparent_keep_world(node, Some(1));
+    }
+}
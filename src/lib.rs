@@ -1,5 +1,8 @@
-#![feature(portable_simd)]
-#![feature(const_fn_floating_point_arithmetic)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+#![allow(clippy::needless_return)]
+
+// Generic numeric traits
+pub mod traits;
 
 // Vectors
 pub mod vector2;
@@ -7,11 +10,31 @@ pub mod vector3;
 pub mod vector4;
 
 pub mod quaternion;
+pub mod dual_quaternion;
 
-// Rects
+// Rects and boxes
 pub mod rect;
+pub mod aabb;
+
+// Geometric primitives
+pub mod ray;
+pub mod plane;
+pub mod frustum;
+pub mod sphere;
 
 // Matrices
 pub mod mat2x2;
 pub mod mat3x3;
-pub mod mat4x4;
\ No newline at end of file
+pub mod mat4x4;
+
+// Transforms
+pub mod transform;
+pub mod hierarchy;
+
+// Concrete types
+pub mod types;
+pub mod prelude;
+
+// Shared test assertions
+#[cfg(test)]
+mod test_helpers;
@@ -77,7 +77,7 @@ macro_rules! gen_mat2x2 {
                 let mut res = $ident { rows: [[0.0; 2]; 2] };
                 for i in 0..2 {
                     for j in 0..2 {
-                        res.rows[i][j] = self.row(i).dot(rhs.col(i));
+                        res.rows[i][j] = self.row(i).dot(rhs.col(j));
                     }
                 }
                 return res;
@@ -112,5 +112,20 @@ macro_rules! gen_mat2x2 {
                 return &mut self.rows[index.0][index.1];
             }
         }
+
+        $crate::matrix_trait_impl!($ident, $vec2, $typ, 2);
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn mul_matches_hand_computed_product() {
+        let a = Mat2d::new([[1.0, 2.0], [3.0, 4.0]]);
+        let b = Mat2d::new([[5.0, 6.0], [7.0, 8.0]]);
+        assert_eq!(&a * &b, Mat2d::new([[19.0, 22.0], [43.0, 50.0]]));
+        assert_eq!(&b * &a, Mat2d::new([[23.0, 34.0], [31.0, 46.0]]));
+    }
+}
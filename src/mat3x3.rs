@@ -94,7 +94,7 @@ macro_rules! gen_mat3x3 {
                 let mut res = $ident { rows: [[0.0; 3]; 3] };
                 for i in 0..3 {
                     for j in 0..3 {
-                        res.rows[i][j] = self.row(i).dot(rhs.col(i));
+                        res.rows[i][j] = self.row(i).dot(rhs.col(j));
                     }
                 }
                 return res;
@@ -130,6 +130,8 @@ macro_rules! gen_mat3x3 {
                 return &mut self.rows[index.0][index.1];
             }
         }
+
+        $crate::matrix_trait_impl!($ident, $vec3, $typ, 3);
     };
 }
 
@@ -147,6 +149,172 @@ macro_rules! impl_tf3x3 {
                     [0.0, 0.0, 1.0],
                 ]}
             }
+
+            /// Splits a matrix built like `tf_matrix` back into `(pos, rot, scale)`. A reflection is returned
+            /// as a negative x scale. Returns `None` if the matrix has shear, a projective part or a zero scale.
+            pub fn decompose(&self) -> Option<($vec2, $typ, $vec2)> {
+                let eps = <$typ>::EPSILON.sqrt();
+                if self.rows[2][0].abs() > eps || self.rows[2][1].abs() > eps || (self.rows[2][2] - 1.0).abs() > eps {
+                    return None;
+                }
+
+                let x = $vec2(self.rows[0][0], self.rows[1][0]);
+                let y = $vec2(self.rows[0][1], self.rows[1][1]);
+
+                let mut scale = $vec2(x.magnitude(), y.magnitude());
+                if scale.min_axis() <= eps * scale.max_axis() {
+                    return None;
+                }
+
+                let (mut nx, ny) = (x / scale.0, y / scale.1);
+                if nx.dot(ny).abs() > eps {
+                    return None;
+                }
+
+                if nx.0 * ny.1 - nx.1 * ny.0 < 0.0 {
+                    scale.0 = -scale.0;
+                    nx = -nx;
+                }
+
+                let pos = $vec2(self.rows[0][2], self.rows[1][2]);
+                return Some((pos, nx.1.atan2(nx.0), scale));
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_rot3x3 {
+    ($ident:ident, $quat:ident, $typ:ty) => {
+        impl $ident {
+            /// Builds the rotation matrix of a unit quaternion.
+            pub fn from_quat(rot: $quat) -> Self {
+                let $quat(w, x, y, z) = rot;
+
+                return Self { rows: [
+                    [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z),       2.0 * (x * z + w * y)      ],
+                    [2.0 * (x * y + w * z),       1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)      ],
+                    [2.0 * (x * z - w * y),       2.0 * (y * z + w * x),       1.0 - 2.0 * (x * x + y * y)],
+                ]};
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_rect3x3 {
+    ($ident:ident, $rect:ident) => {
+        impl $ident {
+            /// Affine matrix mapping `src` onto `dst`, like `src.map_to(&dst, pos)`. Stretches if the aspect
+            /// ratios differ.
+            pub fn rect_to_rect(src: &$rect, dst: &$rect) -> Self {
+                let scale = dst.size().inv_scale(src.size());
+                let pos = dst.start - src.start.scale(scale);
+
+                return Self { rows: [
+                    [scale.0, 0.0,     pos.0],
+                    [0.0,     scale.1, pos.1],
+                    [0.0,     0.0,     1.0  ],
+                ]};
+            }
+
+            /// Maps `src` into the biggest part of `dst` with the same aspect ratio, centered, leaving bars
+            /// on two sides.
+            pub fn rect_to_rect_fit(src: &$rect, dst: &$rect) -> Self {
+                return Self::rect_to_rect(src, &dst.letterbox(src.aspect_ratio()));
+            }
+
+            /// Maps the centered part of `src` with the aspect ratio of `dst` onto `dst`, covering all of it
+            /// and cropping the rest of `src`.
+            pub fn rect_to_rect_fill(src: &$rect, dst: &$rect) -> Self {
+                return Self::rect_to_rect(&src.letterbox(dst.aspect_ratio()), dst);
+            }
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::{assert_mat_eq, assert_vec_eq, mat3_point};
+
+    #[test]
+    fn mul_matches_hand_computed_product() {
+        let a = Mat3d::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 10.0]]);
+        let b = Mat3d::new([[2.0, 0.0, 1.0], [1.0, 3.0, 0.0], [0.0, 1.0, 4.0]]);
+        assert_eq!(&a * &b, Mat3d::new([[4.0, 9.0, 13.0], [13.0, 21.0, 28.0], [22.0, 34.0, 47.0]]));
+    }
+
+    #[test]
+    fn tf_matrix_rotates_counter_clockwise() {
+        let mat = Mat3d::tf_matrix(Vec2d(1.0, 2.0), std::f64::consts::FRAC_PI_2, Vec2d(2.0, 1.0));
+        assert_vec_eq(mat3_point(&mat, Vec2d::ZERO), Vec2d(1.0, 2.0));
+        assert_vec_eq(mat3_point(&mat, Vec2d(1.0, 0.0)), Vec2d(1.0, 4.0));
+        assert_vec_eq(mat3_point(&mat, Vec2d(0.0, 1.0)), Vec2d(0.0, 2.0));
+    }
+
+    #[test]
+    fn decompose_round_trip() {
+        for rot in [0.0, 0.7, -2.5, 3.0] {
+            for scale in [Vec2d::ONE, Vec2d(2.0, 0.5), Vec2d(-2.0, 0.5), Vec2d(1.0, -4.0), Vec2d::one(-1.0)] {
+                let pos = Vec2d(1.0, -2.0);
+                let mat = Mat3d::tf_matrix(pos, rot, scale);
+                let (dec_pos, dec_rot, dec_scale) = mat.decompose().unwrap();
+                assert_eq!(dec_pos, pos);
+                assert_mat_eq(&Mat3d::tf_matrix(dec_pos, dec_rot, dec_scale), &mat);
+                assert!(dec_scale.1 > 0.0, "{:?}", dec_scale);
+            }
+        }
+
+        let (_, rot, scale) = Mat3d::tf_matrix(Vec2d::ZERO, 0.7, Vec2d(-2.0, 0.5)).decompose().unwrap();
+        assert!((rot - 0.7).abs() < 1e-9);
+        assert!((scale - Vec2d(-2.0, 0.5)).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn decompose_rejects_non_trs_matrices() {
+        let mut sheared = Mat3d::tf_matrix(Vec2d(1.0, 2.0), 0.0, Vec2d::ONE);
+        sheared[(0, 1)] = 0.5;
+        assert_eq!(sheared.decompose(), None);
+
+        let mut projective = Mat3d::tf_matrix(Vec2d::ZERO, 0.0, Vec2d::ONE);
+        projective[(2, 0)] = 1.0;
+        assert_eq!(projective.decompose(), None);
+
+        assert_eq!(Mat3d::tf_matrix(Vec2d::ZERO, 0.7, Vec2d(0.0, 1.0)).decompose(), None);
+    }
+
+    #[test]
+    fn from_quat_rotates_like_the_quaternion() {
+        let rot = Quatd::rotator(Vec3d(0.3, -1.2, 0.7));
+        let point = Vec3d(0.5, -2.0, 3.0);
+        assert!((&Mat3d::from_quat(rot) * point - point.rotate(rot)).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn rect_to_rect_matches_map_to() {
+        let src = Rectd::new(Vec2d(1.0, 2.0), Vec2d(5.0, 4.0));
+        let dst = Rectd::new(Vec2d(-10.0, 0.0), Vec2d(10.0, 30.0));
+        let mat = Mat3d::rect_to_rect(&src, &dst);
+        for pos in [src.start, src.end, Vec2d(2.0, 3.5), Vec2d(-4.0, 10.0)] {
+            assert!((mat3_point(&mat, pos) - src.map_to(&dst, pos)).magnitude() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn rect_to_rect_fit_and_fill() {
+        let src = Rectd::new(Vec2d(0.0, 0.0), Vec2d(4.0, 2.0));
+        let dst = Rectd::new(Vec2d(0.0, 0.0), Vec2d(10.0, 10.0));
+
+        // fit: the whole source is visible, bars above and below
+        let fit = Mat3d::rect_to_rect_fit(&src, &dst);
+        assert_eq!(mat3_point(&fit, src.start), Vec2d(0.0, 2.5));
+        assert_eq!(mat3_point(&fit, src.end), Vec2d(10.0, 7.5));
+
+        // fill: the destination is covered, the source is cropped left and right
+        let fill = Mat3d::rect_to_rect_fill(&src, &dst);
+        assert_eq!(mat3_point(&fill, Vec2d(1.0, 0.0)), dst.start);
+        assert_eq!(mat3_point(&fill, Vec2d(3.0, 2.0)), dst.end);
+        assert_eq!(mat3_point(&fill, src.start), Vec2d(-5.0, 0.0));
+    }
+}
@@ -165,6 +165,8 @@ macro_rules! gen_mat4x4 {
                 return &mut self.rows[index.0][index.1];
             }
         }
+
+        $crate::matrix_trait_impl!($ident, $vec4, $typ, 4);
     };
 }
 
@@ -185,6 +187,50 @@ macro_rules! impl_tf4x4 {
                 ]}
             }
 
+            /// Splits a matrix built like `tf_matrix` back into `(pos, rot, scale)`. A reflection is returned
+            /// as a negative x scale. Returns `None` if the matrix has shear, a projective part or a zero scale.
+            pub fn decompose(&self) -> Option<($vec3, $quat, $vec3)> {
+                let eps = <$typ>::EPSILON.sqrt();
+                if self.rows[3][0].abs() > eps || self.rows[3][1].abs() > eps || self.rows[3][2].abs() > eps || (self.rows[3][3] - 1.0).abs() > eps {
+                    return None;
+                }
+
+                let mut x = $vec3(self.rows[0][0], self.rows[1][0], self.rows[2][0]);
+                let y = $vec3(self.rows[0][1], self.rows[1][1], self.rows[2][1]);
+                let z = $vec3(self.rows[0][2], self.rows[1][2], self.rows[2][2]);
+
+                let mut scale = $vec3(x.magnitude(), y.magnitude(), z.magnitude());
+                if scale.min_axis() <= eps * scale.max_axis() {
+                    return None;
+                }
+
+                let (nx, ny, nz) = (x / scale.0, y / scale.1, z / scale.2);
+                if nx.dot(ny).abs() > eps || nx.dot(nz).abs() > eps || ny.dot(nz).abs() > eps {
+                    return None;
+                }
+
+                if nx.cross(ny).dot(nz) < 0.0 {
+                    scale.0 = -scale.0;
+                    x = -x;
+                }
+
+                let pos = $vec3(self.rows[0][3], self.rows[1][3], self.rows[2][3]);
+                let rot = $quat::from_basis(x / scale.0.abs(), ny, nz);
+                return Some((pos, rot, scale));
+            }
+
+            /// Builds the rotation matrix of a unit quaternion.
+            pub fn from_quat(rot: $quat) -> Self {
+                let $quat(w, x, y, z) = rot;
+
+                return Self { rows: [
+                    [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z),       2.0 * (x * z + w * y),       0.0],
+                    [2.0 * (x * y + w * z),       1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x),       0.0],
+                    [2.0 * (x * z - w * y),       2.0 * (y * z + w * x),       1.0 - 2.0 * (x * x + y * y), 0.0],
+                    [0.0,                         0.0,                         0.0,                         1.0],
+                ]};
+            }
+
             pub fn proj_matrix(aspect_ratio: $typ, fov: $typ, near: $typ, far: $typ) -> Self {
                 let tan = (fov * 0.5).tan();
                 return Self { rows: [
@@ -204,6 +250,542 @@ macro_rules! impl_tf4x4 {
                     [0.0,                         0.0,        1.0,                 0.0                      ],
                 ]};
             }
+
+            /// Reverse-Z version of `proj_matrix`, mapping `near` to depth 1 and `far` to depth 0, which spreads
+            /// the float precision much more evenly. Needs a `GREATER` depth test and clearing depth to 0.
+            pub fn proj_matrix_reverse_z(aspect_ratio: $typ, fov: $typ, near: $typ, far: $typ) -> Self {
+                return Self::perspective(aspect_ratio, fov, 1.0, -near / (far - near), far * near / (far - near));
+            }
+
+            /// Vulkan version of `proj_matrix_reverse_z`, with the y axis flipped.
+            pub fn proj_matrix_reverse_z_vk(aspect_ratio: $typ, fov: $typ, near: $typ, far: $typ) -> Self {
+                return Self::perspective(aspect_ratio, fov, -1.0, -near / (far - near), far * near / (far - near));
+            }
+
+            /// `proj_matrix` with the far plane at infinity, depth goes from 0 at `near` towards 1.
+            pub fn proj_matrix_infinite(aspect_ratio: $typ, fov: $typ, near: $typ) -> Self {
+                return Self::perspective(aspect_ratio, fov, 1.0, 1.0, -near);
+            }
+
+            /// Vulkan version of `proj_matrix_infinite`, with the y axis flipped.
+            pub fn proj_matrix_infinite_vk(aspect_ratio: $typ, fov: $typ, near: $typ) -> Self {
+                return Self::perspective(aspect_ratio, fov, -1.0, 1.0, -near);
+            }
+
+            /// Reverse-Z projection with the far plane at infinity, depth goes from 1 at `near` towards 0.
+            /// The best precision of all variants.
+            pub fn proj_matrix_infinite_reverse_z(aspect_ratio: $typ, fov: $typ, near: $typ) -> Self {
+                return Self::perspective(aspect_ratio, fov, 1.0, 0.0, near);
+            }
+
+            /// Vulkan version of `proj_matrix_infinite_reverse_z`, with the y axis flipped.
+            pub fn proj_matrix_infinite_reverse_z_vk(aspect_ratio: $typ, fov: $typ, near: $typ) -> Self {
+                return Self::perspective(aspect_ratio, fov, -1.0, 0.0, near);
+            }
+
+            // symmetric perspective where the depth row is `z * z_scale + z_offset`
+            fn perspective(aspect_ratio: $typ, fov: $typ, y_sign: $typ, z_scale: $typ, z_offset: $typ) -> Self {
+                let tan = (fov * 0.5).tan();
+                return Self { rows: [
+                    [1.0 / (aspect_ratio * tan), 0.0,          0.0,     0.0     ],
+                    [0.0,                        y_sign / tan, 0.0,     0.0     ],
+                    [0.0,                        0.0,          z_scale, z_offset],
+                    [0.0,                        0.0,          1.0,     0.0     ],
+                ]};
+            }
+
+            /// Converts a depth buffer value of `proj_matrix` (or its off-center and Vulkan versions) back to
+            /// the view space distance along z.
+            pub fn linearize_depth(depth: $typ, near: $typ, far: $typ) -> $typ {
+                return far * near / (far - depth * (far - near));
+            }
+
+            /// Converts a depth buffer value of `proj_matrix_reverse_z` back to the view space distance along z.
+            pub fn linearize_depth_reverse_z(depth: $typ, near: $typ, far: $typ) -> $typ {
+                return far * near / (near + depth * (far - near));
+            }
+
+            /// Converts a depth buffer value of `proj_matrix_infinite` back to the view space distance along z.
+            pub fn linearize_depth_infinite(depth: $typ, near: $typ) -> $typ {
+                return near / (1.0 - depth);
+            }
+
+            /// Converts a depth buffer value of `proj_matrix_infinite_reverse_z` back to the view space
+            /// distance along z.
+            pub fn linearize_depth_infinite_reverse_z(depth: $typ, near: $typ) -> $typ {
+                return near / depth;
+            }
+
+            /// Converts a depth buffer value of `orthographic` back to the view space distance along z.
+            pub fn linearize_depth_orthographic(depth: $typ, near: $typ, far: $typ) -> $typ {
+                return near + depth * (far - near);
+            }
+
+            /// Perspective projection with an asymmetric frustum, `left`, `right`, `bottom` and `top` being
+            /// the frustum edges on the near plane. Same conventions as `proj_matrix`.
+            pub fn proj_matrix_off_center(left: $typ, right: $typ, bottom: $typ, top: $typ, near: $typ, far: $typ) -> Self {
+                return Self { rows: [
+                    [2.0 * near / (right - left), 0.0,                         -(right + left) / (right - left), 0.0                       ],
+                    [0.0,                         2.0 * near / (top - bottom), -(top + bottom) / (top - bottom), 0.0                       ],
+                    [0.0,                         0.0,                         far / (far - near),               -far * near / (far - near)],
+                    [0.0,                         0.0,                         1.0,                              0.0                       ],
+                ]};
+            }
+
+            /// Vulkan version of `proj_matrix_off_center`, with the y axis flipped.
+            pub fn proj_matrix_off_center_vk(left: $typ, right: $typ, bottom: $typ, top: $typ, near: $typ, far: $typ) -> Self {
+                let mut res = Self::proj_matrix_off_center(left, right, bottom, top, near, far);
+                for x in res.rows[1].iter_mut() {
+                    *x = -*x;
+                }
+                return res;
+            }
+
+            /// Orthographic projection of the box between `left` and `right`, `bottom` and `top`, `near` and `far`,
+            /// mapping depth to `[0, 1]` like `proj_matrix`.
+            pub fn orthographic(left: $typ, right: $typ, bottom: $typ, top: $typ, near: $typ, far: $typ) -> Self {
+                return Self { rows: [
+                    [2.0 / (right - left), 0.0,                  0.0,                -(right + left) / (right - left)],
+                    [0.0,                  2.0 / (top - bottom), 0.0,                -(top + bottom) / (top - bottom)],
+                    [0.0,                  0.0,                  1.0 / (far - near), -near / (far - near)            ],
+                    [0.0,                  0.0,                  0.0,                1.0                             ],
+                ]};
+            }
+
+            /// Vulkan version of `orthographic`, with the y axis flipped.
+            pub fn orthographic_vk(left: $typ, right: $typ, bottom: $typ, top: $typ, near: $typ, far: $typ) -> Self {
+                let mut res = Self::orthographic(left, right, bottom, top, near, far);
+                for x in res.rows[1].iter_mut() {
+                    *x = -*x;
+                }
+                return res;
+            }
+
+            /// View matrix of a camera at `eye` looking at `target`, with +z forward in view space as expected
+            /// by `proj_matrix`. The crate is left-handed throughout, so there is no right-handed version.
+            pub fn look_at_lh(eye: $vec3, target: $vec3, up: $vec3) -> Self {
+                let z = (target - eye).normalized();
+                let x = up.cross(z).normalized();
+                let y = z.cross(x);
+
+                return Self { rows: [
+                    [x.0, x.1, x.2, -x.dot(eye)],
+                    [y.0, y.1, y.2, -y.dot(eye)],
+                    [z.0, z.1, z.2, -z.dot(eye)],
+                    [0.0, 0.0, 0.0, 1.0        ],
+                ]};
+            }
+
+            /// Replaces the near plane of a perspective projection (GL or Vulkan) by the view space plane
+            /// `normal . p + dist = 0`, keeping everything on the side `normal` points to (Lengyel's oblique
+            /// near-plane clipping), e.g. to clip reflections at the water surface. The camera has to be on the
+            /// clipped side. Returns `None` if the projection can't be inverted.
+            pub fn oblique_near_plane(&self, normal: $vec3, dist: $typ) -> Option<Self> {
+                let plane = [normal.0, normal.1, normal.2, dist];
+                let inv = self.inverse()?;
+
+                // plane in clip space, to find the frustum corner opposite to it
+                let mut clip_plane = [0.0; 4];
+                for j in 0..4 {
+                    for i in 0..4 {
+                        clip_plane[j] += plane[i] * inv.rows[i][j];
+                    }
+                }
+
+                let corner = [clip_plane[0].signum(), clip_plane[1].signum(), 1.0, 1.0];
+                let mut q = [0.0; 4];
+                for i in 0..4 {
+                    for j in 0..4 {
+                        q[i] += inv.rows[i][j] * corner[j];
+                    }
+                }
+
+                let mut w_dot = 0.0;
+                let mut plane_dot = 0.0;
+                for i in 0..4 {
+                    w_dot += self.rows[3][i] * q[i];
+                    plane_dot += plane[i] * q[i];
+                }
+
+                let scale = w_dot / plane_dot;
+                let mut res = self.clone();
+                for i in 0..4 {
+                    res.rows[2][i] = plane[i] * scale;
+                }
+                return Some(res);
+            }
         }
     };
 }
+
+#[macro_export]
+macro_rules! impl_screen4x4 {
+    ($ident:ident, $vec2:ident, $vec3:ident, $vec4:ident, $rect:ident, $ray:ident, $typ:ty) => {
+        /// Conversions between world space and the screen for a `proj * view` matrix. Pixels have their origin
+        /// in the top left corner of the screen and y pointing down, so `viewport.start` is the top left corner
+        /// of the viewport and `viewport.end` the bottom right one. Pixel positions aren't offset to pixel
+        /// centers, the NDC corner `(-1, 1)` lands exactly on `viewport.start`. The `_vk` versions are for the
+        /// Vulkan projections, whose y axis is already flipped.
+        impl $ident {
+            /// Returns the pixel position and the depth of a world point, `None` if it is behind the camera.
+            pub fn project(&self, point: $vec3, viewport: $rect) -> Option<$vec3> {
+                return self.project_impl(point, viewport, -1.0);
+            }
+
+            pub fn project_vk(&self, point: $vec3, viewport: $rect) -> Option<$vec3> {
+                return self.project_impl(point, viewport, 1.0);
+            }
+
+            /// Returns the world point under the pixel at the given depth buffer value, `None` if the matrix
+            /// can't be inverted or the depth is at infinity.
+            pub fn unproject(&self, pixel: $vec2, depth: $typ, viewport: $rect) -> Option<$vec3> {
+                return self.unproject_impl(pixel, depth, viewport, -1.0);
+            }
+
+            pub fn unproject_vk(&self, pixel: $vec2, depth: $typ, viewport: $rect) -> Option<$vec3> {
+                return self.unproject_impl(pixel, depth, viewport, 1.0);
+            }
+
+            /// Returns the ray from the near plane through the pixel, for picking. Works with every projection
+            /// of `impl_tf4x4`, including reverse-Z and infinite far planes.
+            pub fn screen_ray(&self, viewport: $rect, pixel: $vec2) -> Option<$ray> {
+                return self.screen_ray_impl(viewport, pixel, -1.0);
+            }
+
+            pub fn screen_ray_vk(&self, viewport: $rect, pixel: $vec2) -> Option<$ray> {
+                return self.screen_ray_impl(viewport, pixel, 1.0);
+            }
+
+            fn project_impl(&self, point: $vec3, viewport: $rect, y_sign: $typ) -> Option<$vec3> {
+                let clip = $crate::traits::Matrix::mul_vec(self, $vec4::from_xyz(point, 1.0));
+                if clip.3 <= 0.0 {
+                    return None;
+                }
+
+                let ndc = clip.xyz() / clip.3;
+                let size = viewport.size();
+                return Some($vec3(
+                    viewport.start.0 + (ndc.0 + 1.0) * 0.5 * size.0,
+                    viewport.start.1 + (ndc.1 * y_sign + 1.0) * 0.5 * size.1,
+                    ndc.2,
+                ));
+            }
+
+            fn unproject_impl(&self, pixel: $vec2, depth: $typ, viewport: $rect, y_sign: $typ) -> Option<$vec3> {
+                let world = self.inverse()?.unproject_homogeneous(pixel, depth, viewport, y_sign);
+                if world.3 == 0.0 {
+                    return None;
+                }
+                return Some(world.xyz() / world.3);
+            }
+
+            fn screen_ray_impl(&self, viewport: $rect, pixel: $vec2, y_sign: $typ) -> Option<$ray> {
+                let inv = self.inverse()?;
+                let mut near = inv.unproject_homogeneous(pixel, 0.0, viewport, y_sign);
+                let mut far = inv.unproject_homogeneous(pixel, 1.0, viewport, y_sign);
+
+                // w is the inverse of the view distance, so the point with the bigger w is the near one,
+                // which is depth 1 with reverse-Z
+                if far.3 > near.3 {
+                    std::mem::swap(&mut near, &mut far);
+                }
+                if near.3 <= 0.0 {
+                    return None;
+                }
+
+                let origin = near.xyz() / near.3;
+                // stays valid when the far point is at infinity (w = 0)
+                let dir = far.xyz() - origin * far.3;
+                return Some($ray::new(origin, dir.normalized()));
+            }
+
+            fn unproject_homogeneous(&self, pixel: $vec2, depth: $typ, viewport: $rect, y_sign: $typ) -> $vec4 {
+                let ndc = (pixel - viewport.start).inv_scale(viewport.size()) * 2.0 - $vec2::ONE;
+                return $crate::traits::Matrix::mul_vec(self, $vec4(ndc.0, ndc.1 * y_sign, depth, 1.0));
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::{assert_mat_eq, assert_vec_eq, mat4_point};
+
+    #[test]
+    fn decompose_round_trip() {
+        let rots = [Quatd::IDENT, Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Quatd::rotator(Vec3d(0.0, 3.0, 0.0))];
+        let scales = [Vec3d::ONE, Vec3d(2.0, 0.5, 3.0), Vec3d(-2.0, 0.5, 3.0), Vec3d(1.0, -4.0, 0.3), Vec3d::one(-1.0)];
+        for rot in rots {
+            for scale in scales {
+                let pos = Vec3d(1.0, -2.0, 3.0);
+                let mat = Mat4d::tf_matrix(pos, rot, scale);
+                let (dec_pos, dec_rot, dec_scale) = mat.decompose().unwrap();
+                assert_eq!(dec_pos, pos);
+                assert_mat_eq(&Mat4d::tf_matrix(dec_pos, dec_rot, dec_scale), &mat);
+
+                // a reflection always comes back as a negative x scale
+                assert!(dec_scale.1 > 0.0 && dec_scale.2 > 0.0, "{:?}", dec_scale);
+                assert_eq!(dec_scale.0 < 0.0, scale.0 * scale.1 * scale.2 < 0.0);
+            }
+        }
+
+        let (_, rot, scale) = Mat4d::tf_matrix(Vec3d::ZERO, rots[1], Vec3d(-2.0, 0.5, 3.0)).decompose().unwrap();
+        assert!(rot.dot(rots[1]).abs() > 1.0 - 1e-9);
+        assert!((scale - Vec3d(-2.0, 0.5, 3.0)).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn decompose_rejects_non_trs_matrices() {
+        let mut sheared = Mat4d::tf_matrix(Vec3d(1.0, 2.0, 3.0), Quatd::IDENT, Vec3d::ONE);
+        sheared[(0, 1)] = 0.5;
+        assert_eq!(sheared.decompose(), None);
+
+        let mut projective = Mat4d::tf_matrix(Vec3d::ZERO, Quatd::IDENT, Vec3d::ONE);
+        projective[(3, 2)] = 1.0;
+        assert_eq!(projective.decompose(), None);
+        assert_eq!(Mat4d::proj_matrix(1.5, 1.0, 0.1, 100.0).decompose(), None);
+
+        let flat = Mat4d::tf_matrix(Vec3d::ZERO, Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d(1.0, 0.0, 2.0));
+        assert_eq!(flat.decompose(), None);
+    }
+
+    #[test]
+    fn from_quat_matches_tf_matrix() {
+        let rot = Quatd::rotator(Vec3d(0.3, -1.2, 0.7));
+        assert_mat_eq(&Mat4d::from_quat(rot), &Mat4d::tf_matrix(Vec3d::ZERO, rot, Vec3d::ONE));
+        assert_mat_eq(&Mat4d::from_quat(Quatd::IDENT), &Mat4d::IDENTITY);
+    }
+
+    #[test]
+    fn tf_matrix_inverse() {
+        let mat = Mat4d::tf_matrix(Vec3d(1.0, -2.0, 3.0), Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d(2.0, 0.5, -3.0));
+        assert_mat_eq(&(&mat * &mat.inverse().unwrap()), &Mat4d::IDENTITY);
+        assert!((mat.determinant() + 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn orthographic_maps_the_box() {
+        let ortho = Mat4d::orthographic(-4.0, 2.0, -1.0, 3.0, 0.5, 10.0);
+        assert_vec_eq(mat4_point(&ortho, Vec3d(-4.0, -1.0, 0.5)), Vec3d(-1.0, -1.0, 0.0));
+        assert_vec_eq(mat4_point(&ortho, Vec3d(2.0, 3.0, 10.0)), Vec3d(1.0, 1.0, 1.0));
+        assert_vec_eq(mat4_point(&ortho, Vec3d(-1.0, 1.0, 5.25)), Vec3d(0.0, 0.0, 0.5));
+
+        let ortho_vk = Mat4d::orthographic_vk(-4.0, 2.0, -1.0, 3.0, 0.5, 10.0);
+        assert_vec_eq(mat4_point(&ortho_vk, Vec3d(2.0, 3.0, 10.0)), Vec3d(1.0, -1.0, 1.0));
+    }
+
+    #[test]
+    fn off_center_maps_the_frustum() {
+        let (near, far) = (0.5, 20.0);
+        let proj = Mat4d::proj_matrix_off_center(-0.2, 0.6, -0.1, 0.3, near, far);
+        assert_vec_eq(mat4_point(&proj, Vec3d(-0.2, -0.1, near)), Vec3d(-1.0, -1.0, 0.0));
+        let scale = far / near;
+        assert_vec_eq(mat4_point(&proj, Vec3d(0.6 * scale, 0.3 * scale, far)), Vec3d(1.0, 1.0, 1.0));
+
+        let proj_vk = Mat4d::proj_matrix_off_center_vk(-0.2, 0.6, -0.1, 0.3, near, far);
+        assert_vec_eq(mat4_point(&proj_vk, Vec3d(-0.2, -0.1, near)), Vec3d(-1.0, 1.0, 0.0));
+
+        // a centered frustum is the same as proj_matrix
+        let (aspect, fov) = (1.5, 1.1);
+        let top = near * (fov * 0.5).tan();
+        let centered = Mat4d::proj_matrix_off_center(-top * aspect, top * aspect, -top, top, near, far);
+        assert_mat_eq(&centered, &Mat4d::proj_matrix(aspect, fov, near, far));
+        let centered_vk = Mat4d::proj_matrix_off_center_vk(-top * aspect, top * aspect, -top, top, near, far);
+        assert_mat_eq(&centered_vk, &Mat4d::proj_matrix_vk(aspect, fov, near, far));
+    }
+
+    #[test]
+    fn look_at_puts_the_target_on_the_view_axis() {
+        let eye = Vec3d(1.0, 2.0, -3.0);
+        let target = Vec3d(4.0, 0.0, 1.0);
+        let distance = (target - eye).magnitude();
+
+        let view = Mat4d::look_at_lh(eye, target, Vec3d::UP);
+        assert_vec_eq(mat4_point(&view, eye), Vec3d::ZERO);
+        assert_vec_eq(mat4_point(&view, target), Vec3d(0.0, 0.0, distance));
+        assert!(mat4_point(&view, eye + Vec3d::UP).1 > 0.0);
+        assert!((view.determinant() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn oblique_near_plane_clips_at_the_plane() {
+        for proj in [Mat4d::proj_matrix(1.5, 1.1, 0.1, 100.0), Mat4d::proj_matrix_vk(1.5, 1.1, 0.1, 100.0)] {
+            let normal = Vec3d(0.0, 0.3, 1.0).normalized();
+            let on_plane = Vec3d(0.0, 0.0, 5.0);
+            let dist = -normal.dot(on_plane);
+            let oblique = proj.oblique_near_plane(normal, dist).unwrap();
+
+            for point in [on_plane, Vec3d(1.0, 0.0, 5.0), Vec3d(0.0, 1.0, 5.0 - 0.3)] {
+                assert!(mat4_point(&oblique, point).2.abs() < 1e-9, "{:?}", mat4_point(&oblique, point));
+            }
+            assert!(mat4_point(&oblique, Vec3d(0.0, 0.0, 4.0)).2 < 0.0);
+            assert!(mat4_point(&oblique, Vec3d(0.0, 0.0, 8.0)).2 > 0.0);
+
+            // x and y are unchanged
+            let point = Vec3d(0.5, -0.25, 7.0);
+            assert!((mat4_point(&oblique, point).0 - mat4_point(&proj, point).0).abs() < 1e-9);
+            assert!((mat4_point(&oblique, point).1 - mat4_point(&proj, point).1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn linearize_depth_inverts_every_projection() {
+        let (aspect, fov, near, far) = (1.5, 1.1, 0.1, 100.0);
+        let depths = [near, 0.5, 7.0, 60.0, far];
+        let depth_of = |mat: &Mat4d, z: f64| mat4_point(mat, Vec3d(0.3, -0.2, 1.0) * z).2;
+        let assert_close = |a: f64, b: f64| assert!((a - b).abs() < 1e-9 * b.max(1.0), "{} != {}", a, b);
+
+        for proj in [
+            Mat4d::proj_matrix(aspect, fov, near, far),
+            Mat4d::proj_matrix_vk(aspect, fov, near, far),
+            Mat4d::proj_matrix_off_center(-0.2, 0.6, -0.1, 0.3, near, far),
+            Mat4d::proj_matrix_off_center_vk(-0.2, 0.6, -0.1, 0.3, near, far),
+        ] {
+            assert_close(depth_of(&proj, near), 0.0);
+            assert_close(depth_of(&proj, far), 1.0);
+            for z in depths {
+                assert_close(Mat4d::linearize_depth(depth_of(&proj, z), near, far), z);
+            }
+        }
+
+        for proj in [Mat4d::proj_matrix_reverse_z(aspect, fov, near, far), Mat4d::proj_matrix_reverse_z_vk(aspect, fov, near, far)] {
+            assert_close(depth_of(&proj, near), 1.0);
+            assert_close(depth_of(&proj, far), 0.0);
+            for z in depths {
+                assert_close(Mat4d::linearize_depth_reverse_z(depth_of(&proj, z), near, far), z);
+            }
+        }
+
+        for proj in [Mat4d::proj_matrix_infinite(aspect, fov, near), Mat4d::proj_matrix_infinite_vk(aspect, fov, near)] {
+            assert_close(depth_of(&proj, near), 0.0);
+            assert!(depth_of(&proj, 1e6) < 1.0);
+            for z in depths {
+                assert_close(Mat4d::linearize_depth_infinite(depth_of(&proj, z), near), z);
+            }
+        }
+
+        for proj in [
+            Mat4d::proj_matrix_infinite_reverse_z(aspect, fov, near),
+            Mat4d::proj_matrix_infinite_reverse_z_vk(aspect, fov, near),
+        ] {
+            assert_close(depth_of(&proj, near), 1.0);
+            assert!(depth_of(&proj, 1e6) > 0.0);
+            for z in depths {
+                assert_close(Mat4d::linearize_depth_infinite_reverse_z(depth_of(&proj, z), near), z);
+            }
+        }
+
+        for proj in [Mat4d::orthographic(-4.0, 2.0, -1.0, 3.0, near, far), Mat4d::orthographic_vk(-4.0, 2.0, -1.0, 3.0, near, far)] {
+            for z in depths {
+                assert_close(Mat4d::linearize_depth_orthographic(depth_of(&proj, z), near, far), z);
+            }
+        }
+    }
+
+    #[test]
+    fn projection_variants_share_x_and_y() {
+        let (aspect, fov, near, far) = (1.5, 1.1, 0.1, 100.0);
+        let point = Vec3d(0.4, -0.3, 2.0);
+        let reference = mat4_point(&Mat4d::proj_matrix(aspect, fov, near, far), point);
+
+        for proj in [
+            Mat4d::proj_matrix_reverse_z(aspect, fov, near, far),
+            Mat4d::proj_matrix_infinite(aspect, fov, near),
+            Mat4d::proj_matrix_infinite_reverse_z(aspect, fov, near),
+        ] {
+            let res = mat4_point(&proj, point);
+            assert!((res.0 - reference.0).abs() < 1e-9 && (res.1 - reference.1).abs() < 1e-9);
+        }
+
+        for proj in [
+            Mat4d::proj_matrix_vk(aspect, fov, near, far),
+            Mat4d::proj_matrix_reverse_z_vk(aspect, fov, near, far),
+            Mat4d::proj_matrix_infinite_vk(aspect, fov, near),
+            Mat4d::proj_matrix_infinite_reverse_z_vk(aspect, fov, near),
+        ] {
+            let res = mat4_point(&proj, point);
+            assert!((res.0 - reference.0).abs() < 1e-9 && (res.1 + reference.1).abs() < 1e-9);
+        }
+    }
+
+    fn camera() -> Mat4d {
+        return Mat4d::look_at_lh(Vec3d(1.0, 2.0, -3.0), Vec3d(1.5, 1.0, 4.0), Vec3d::UP);
+    }
+
+    fn viewport() -> Rectd {
+        return Rectd { start: Vec2d(10.0, 20.0), end: Vec2d(810.0, 620.0) };
+    }
+
+    #[test]
+    fn project_unproject_round_trip() {
+        let (aspect, fov, near, far) = (800.0 / 600.0, 1.1, 0.1, 100.0);
+        let view = camera();
+        let gl = &Mat4d::proj_matrix(aspect, fov, near, far) * &view;
+        let vk = &Mat4d::proj_matrix_vk(aspect, fov, near, far) * &view;
+
+        for point in [Vec3d(1.5, 1.0, 4.0), Vec3d(2.0, 2.5, 0.0), Vec3d(-1.0, 0.0, 10.0)] {
+            let pixel = gl.project(point, viewport()).unwrap();
+            assert_vec_eq(gl.unproject(Vec2d(pixel.0, pixel.1), pixel.2, viewport()).unwrap(), point);
+
+            // both APIs put the point on the same pixel
+            let pixel_vk = vk.project_vk(point, viewport()).unwrap();
+            assert_vec_eq(pixel_vk, pixel);
+            assert_vec_eq(vk.unproject_vk(Vec2d(pixel.0, pixel.1), pixel.2, viewport()).unwrap(), point);
+        }
+
+        // the target is in the center, higher points are closer to the top edge
+        let center = gl.project(Vec3d(1.5, 1.0, 4.0), viewport()).unwrap();
+        assert!((center.0 - 410.0).abs() < 1e-9 && (center.1 - 320.0).abs() < 1e-9);
+        let higher = gl.project(Vec3d(1.5, 2.0, 4.0), viewport()).unwrap();
+        assert!(higher.1 < center.1);
+
+        assert_eq!(gl.project(Vec3d(1.0, 2.0, -10.0), viewport()), None);
+    }
+
+    #[test]
+    fn ndc_corners_land_on_the_viewport_corners() {
+        let top_left = Mat4d::IDENTITY.project(Vec3d(-1.0, 1.0, 0.5), viewport()).unwrap();
+        assert_vec_eq(top_left, Vec3d(10.0, 20.0, 0.5));
+        let bottom_right = Mat4d::IDENTITY.project(Vec3d(1.0, -1.0, 0.5), viewport()).unwrap();
+        assert_vec_eq(bottom_right, Vec3d(810.0, 620.0, 0.5));
+        assert_vec_eq(Mat4d::IDENTITY.unproject(Vec2d(10.0, 20.0), 0.5, viewport()).unwrap(), Vec3d(-1.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn screen_ray_goes_through_the_pixel() {
+        let (aspect, fov, near, far) = (800.0 / 600.0, 1.1, 0.1, 100.0);
+        let view = camera();
+        let point = Vec3d(2.0, 2.5, 0.0);
+
+        let cases = [
+            (Mat4d::proj_matrix(aspect, fov, near, far), false),
+            (Mat4d::proj_matrix_vk(aspect, fov, near, far), true),
+            (Mat4d::proj_matrix_reverse_z(aspect, fov, near, far), false),
+            (Mat4d::proj_matrix_reverse_z_vk(aspect, fov, near, far), true),
+            (Mat4d::proj_matrix_infinite(aspect, fov, near), false),
+            (Mat4d::proj_matrix_infinite_vk(aspect, fov, near), true),
+            (Mat4d::proj_matrix_infinite_reverse_z(aspect, fov, near), false),
+            (Mat4d::proj_matrix_infinite_reverse_z_vk(aspect, fov, near), true),
+        ];
+        for (proj, vulkan) in cases {
+            let view_proj = &proj * &view;
+            let (pixel, ray) = if vulkan {
+                let pixel = view_proj.project_vk(point, viewport()).unwrap();
+                (pixel, view_proj.screen_ray_vk(viewport(), Vec2d(pixel.0, pixel.1)).unwrap())
+            } else {
+                let pixel = view_proj.project(point, viewport()).unwrap();
+                (pixel, view_proj.screen_ray(viewport(), Vec2d(pixel.0, pixel.1)).unwrap())
+            };
+            assert!(pixel.2 > 0.0 && pixel.2 < 1.0);
+
+            // starts on the near plane and passes through the point
+            let eye = Vec3d(1.0, 2.0, -3.0);
+            let forw = (Vec3d(1.5, 1.0, 4.0) - eye).normalized();
+            assert!(((ray.origin - eye).dot(forw) - near).abs() < 1e-9);
+            assert!((ray.dir.magnitude() - 1.0).abs() < 1e-9);
+            let t = (point - ray.origin).dot(ray.dir);
+            assert_vec_eq(ray.at(t), point);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+#[macro_export]
+macro_rules! gen_plane {
+    ($ident:ident, $vec3:ident, $typ:ty) => {
+        /// Plane of the points `p` with `normal . p + dist = 0`. With a normalized `normal`, `dist` is the
+        /// signed distance from the origin to the plane, measured against the normal.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub normal: $vec3,
+            pub dist: $typ,
+        }
+
+        impl $ident {
+            pub const fn new(normal: $vec3, dist: $typ) -> Self {
+                return Self { normal, dist };
+            }
+
+            pub fn from_point_normal(point: $vec3, normal: $vec3) -> Self {
+                let normal = normal.normalized();
+                return Self { normal, dist: -normal.dot(point) };
+            }
+
+            /// Plane through the three points, with the normal along `(b - a) x (c - a)`.
+            pub fn from_points(a: $vec3, b: $vec3, c: $vec3) -> Self {
+                return Self::from_point_normal(a, (b - a).cross(c - a));
+            }
+
+            /// Rescales the plane to a unit normal. Planes without a normal are returned unchanged.
+            pub fn normalized(self) -> Self {
+                let len = self.normal.magnitude();
+                if len == 0.0 {
+                    return self;
+                }
+                return Self { normal: self.normal / len, dist: self.dist / len };
+            }
+
+            /// Distance of the point to the plane, positive on the side the normal points to. Only a
+            /// real distance for normalized planes.
+            pub fn signed_distance(&self, point: $vec3) -> $typ {
+                return self.normal.dot(point) + self.dist;
+            }
+
+            /// Projects the point onto the plane, for normalized planes.
+            pub fn closest_point(&self, point: $vec3) -> $vec3 {
+                return point - self.normal * self.signed_distance(point);
+            }
+
+            /// Same plane with the normal pointing the other way.
+            pub fn flipped(self) -> Self {
+                return Self { normal: -self.normal, dist: -self.dist };
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn from_point_normal_and_distances() {
+        let plane = Planed::from_point_normal(Vec3d(0.0, 2.0, 0.0), Vec3d(0.0, 3.0, 0.0));
+        assert_eq!(plane, Planed::new(Vec3d::UP, -2.0));
+        assert_eq!(plane.signed_distance(Vec3d(5.0, 5.0, -1.0)), 3.0);
+        assert_eq!(plane.signed_distance(Vec3d(5.0, -1.0, -1.0)), -3.0);
+        assert_eq!(plane.closest_point(Vec3d(5.0, 5.0, -1.0)), Vec3d(5.0, 2.0, -1.0));
+
+        let flipped = plane.flipped();
+        assert_eq!(flipped.signed_distance(Vec3d(5.0, 5.0, -1.0)), -3.0);
+    }
+
+    #[test]
+    fn from_points_follows_the_winding() {
+        let (a, b, c) = (Vec3d(1.0, 1.0, 1.0), Vec3d(2.0, 1.0, 1.0), Vec3d(1.0, 2.0, 1.0));
+        let plane = Planed::from_points(a, b, c);
+        assert!((plane.normal - Vec3d::FORW).magnitude() < 1e-12);
+        for point in [a, b, c] {
+            assert!(plane.signed_distance(point).abs() < 1e-12);
+        }
+        assert!((Planed::from_points(a, c, b).normal + Vec3d::FORW).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn normalized_keeps_the_plane() {
+        let plane = Planed::new(Vec3d(0.0, 0.0, 4.0), -8.0).normalized();
+        assert_eq!(plane, Planed::new(Vec3d::FORW, -2.0));
+
+        let degenerate = Planed::new(Vec3d::ZERO, 1.0);
+        assert_eq!(degenerate.normalized(), degenerate);
+    }
+}
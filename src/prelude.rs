@@ -0,0 +1,24 @@
+pub use crate::frustum::Containment;
+pub use crate::hierarchy::{Hierarchy, HierarchyError, HierarchyTransform};
+pub use crate::quaternion::{EulerOrder, QuatAverageMode};
+pub use crate::traits::*;
+pub use crate::types::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_the_concrete_types() {
+        assert_eq!(Vec2f::ONE - Vec2f::ZERO, Vec2f(1.0, 1.0));
+        assert_eq!((Vec2d::ONE.0, Vec2i::ONE.0, Vec2u::ONE.0), (1.0, 1, 1));
+        assert_eq!((Vec3f::ONE.0, Vec3d::ONE.0, Vec3i::ONE.0, Vec3u::ONE.0), (1.0, 1.0, 1, 1));
+        assert_eq!((Vec4f::ONE.0, Vec4d::ONE.0, Vec4i::ONE.0, Vec4u::ONE.0), (1.0, 1.0, 1, 1));
+        assert_eq!((Quatf::IDENT.0, Quatd::IDENT.0), (1.0, 1.0));
+        assert_eq!((Mat2f::IDENTITY.determinant(), Mat2d::IDENTITY.determinant()), (1.0, 1.0));
+        assert_eq!((Mat3f::IDENTITY.determinant(), Mat3d::IDENTITY.determinant()), (1.0, 1.0));
+        assert_eq!((Mat4f::IDENTITY.determinant(), Mat4d::IDENTITY.determinant()), (1.0, 1.0));
+        assert_eq!((Rectf::IDENT.end, Rectd::IDENT.end), (Vec2f::ONE, Vec2d::ONE));
+        assert_eq!((Recti::IDENT.end, Rectu::IDENT.end), (Vec2i::ONE, Vec2u::ONE));
+    }
+}
@@ -1,3 +1,65 @@
+/// Rotation sequence used by the Euler angle conversions of `quat_gen` quaternions.
+///
+/// The axes are listed in the order the rotations are applied, as intrinsic rotations (each one about
+/// the axes already rotated by the previous ones), so `XYZ` builds `rot_x * rot_y * rot_z`.
+/// This is the same as the extrinsic sequence read backwards.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    // Tait-Bryan
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+
+    // Proper Euler
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+impl EulerOrder {
+    /// Indices (0 = x, 1 = y, 2 = z) of the axes in the order they are applied.
+    pub const fn axes(self) -> (usize, usize, usize) {
+        return match self {
+            Self::XYZ => (0, 1, 2),
+            Self::XZY => (0, 2, 1),
+            Self::YXZ => (1, 0, 2),
+            Self::YZX => (1, 2, 0),
+            Self::ZXY => (2, 0, 1),
+            Self::ZYX => (2, 1, 0),
+            Self::XYX => (0, 1, 0),
+            Self::XZX => (0, 2, 0),
+            Self::YXY => (1, 0, 1),
+            Self::YZY => (1, 2, 1),
+            Self::ZXZ => (2, 0, 2),
+            Self::ZYZ => (2, 1, 2),
+        };
+    }
+
+    /// Whether the first and last axes are the same.
+    pub const fn is_proper(self) -> bool {
+        let (a, _, c) = self.axes();
+        return a == c;
+    }
+}
+
+/// Algorithm used by `weighted_average` on `quat_gen` quaternions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuatAverageMode {
+    /// Weighted sum of the sign aligned quaternions, renormalized. Fast and accurate enough
+    /// when the rotations are close to each other.
+    NormalizedSum,
+    /// Eigenvector of the largest eigenvalue of the weighted outer product matrix
+    /// (Markley et al., "Averaging Quaternions"). Exact for any spread of rotations.
+    Markley,
+}
+
 #[macro_export]
 macro_rules! quat_gen {
     ($ident:ident, $vec:ident, $typ:ty) => {
@@ -36,6 +98,155 @@ macro_rules! quat_gen {
                 );
             }
 
+            /// Extracts the rotation from the upper 3x3 part of a matrix (works with both 3x3 and 4x4 matrices)
+            /// using Shepperd's method. Columns are normalized first so scaled or slightly non-orthonormal
+            /// matrices still produce a unit quaternion.
+            pub fn from_rotation_matrix<M: std::ops::Index<(usize, usize), Output = $typ>>(m: &M) -> Self {
+                return Self::from_basis(
+                    $vec(m[(0, 0)], m[(1, 0)], m[(2, 0)]).normalized(),
+                    $vec(m[(0, 1)], m[(1, 1)], m[(2, 1)]).normalized(),
+                    $vec(m[(0, 2)], m[(1, 2)], m[(2, 2)]).normalized(),
+                );
+            }
+
+            /// Builds the rotation that maps `RIGHT`, `UP` and `FORW` to the given orthonormal vectors,
+            /// the inverse of `basis`.
+            pub fn from_basis(x: $vec, y: $vec, z: $vec) -> Self {
+                let c = [
+                    [x.0, y.0, z.0],
+                    [x.1, y.1, z.1],
+                    [x.2, y.2, z.2],
+                ];
+
+                let trace = c[0][0] + c[1][1] + c[2][2];
+                let res = if trace > c[0][0] && trace > c[1][1] && trace > c[2][2] {
+                    let s = (1.0 + trace).sqrt() * 2.0;
+                    Self(0.25 * s, (c[2][1] - c[1][2]) / s, (c[0][2] - c[2][0]) / s, (c[1][0] - c[0][1]) / s)
+                } else if c[0][0] > c[1][1] && c[0][0] > c[2][2] {
+                    let s = (1.0 + c[0][0] - c[1][1] - c[2][2]).sqrt() * 2.0;
+                    Self((c[2][1] - c[1][2]) / s, 0.25 * s, (c[0][1] + c[1][0]) / s, (c[0][2] + c[2][0]) / s)
+                } else if c[1][1] > c[2][2] {
+                    let s = (1.0 + c[1][1] - c[0][0] - c[2][2]).sqrt() * 2.0;
+                    Self((c[0][2] - c[2][0]) / s, (c[0][1] + c[1][0]) / s, 0.25 * s, (c[1][2] + c[2][1]) / s)
+                } else {
+                    let s = (1.0 + c[2][2] - c[0][0] - c[1][1]).sqrt() * 2.0;
+                    Self((c[1][0] - c[0][1]) / s, (c[0][2] + c[2][0]) / s, (c[1][2] + c[2][1]) / s, 0.25 * s)
+                };
+
+                return res.normalized();
+            }
+
+            /// Builds a rotation from Euler angles, given in radians in the order of the axes in `order`
+            /// (`angles.0` is about the first axis). Unlike `rotator_euler` no axis is flipped.
+            pub fn from_euler(order: $crate::quaternion::EulerOrder, angles: $vec) -> Self {
+                let (a, b, c) = order.axes();
+                return Self::axis_rotator(a, angles.0) * Self::axis_rotator(b, angles.1) * Self::axis_rotator(c, angles.2);
+            }
+
+            /// Converts the rotation into Euler angles in the order of the axes in `order`, so that
+            /// `from_euler(order, q.to_euler(order))` gives back `q`. The first and third angles are
+            /// in `[-pi, pi]`, the second in `[-pi/2, pi/2]` for Tait-Bryan orders and `[0, pi]` for proper ones.
+            ///
+            /// In gimbal lock (second angle at `+-pi/2` for Tait-Bryan, `0` or `pi` for proper orders) only the
+            /// combination of the first and third angles is defined, the third angle is then set to 0 and
+            /// the first one holds the whole rotation.
+            pub fn to_euler(self, order: $crate::quaternion::EulerOrder) -> $vec {
+                return self.to_euler_gimbal(order).0;
+            }
+
+            /// Like `to_euler`, but out of all the equivalent sets of angles returns the one closest to `prev`,
+            /// so angles displayed in an editor don't jump around. In gimbal lock the third angle is kept at
+            /// `prev`'s value.
+            pub fn to_euler_closest(self, order: $crate::quaternion::EulerOrder, prev: $vec) -> $vec {
+                let pi = <$typ as $crate::traits::Float>::PI;
+                let (angles, gimbal) = self.to_euler_gimbal(order);
+
+                let candidates = if gimbal {
+                    // only first +- third is known, keep the third one and find which sign matches
+                    let plus = $vec(angles.0 - prev.2, angles.1, prev.2);
+                    let minus = $vec(angles.0 + prev.2, angles.1, prev.2);
+                    let error_plus = self.angle_to(Self::from_euler(order, plus));
+                    let error_minus = self.angle_to(Self::from_euler(order, minus));
+                    [if error_plus <= error_minus { plus } else { minus }; 2]
+                } else if order.is_proper() {
+                    [angles, $vec(angles.0 + pi, -angles.1, angles.2 + pi)]
+                } else {
+                    [angles, $vec(angles.0 + pi, pi - angles.1, angles.2 + pi)]
+                };
+
+                let unwrap = |angle: $typ, target: $typ| angle + ((target - angle) / (2.0 * pi)).round() * 2.0 * pi;
+
+                let mut best = candidates[0];
+                let mut best_dist = <$typ>::INFINITY;
+                for candidate in candidates {
+                    let candidate = $vec(
+                        unwrap(candidate.0, prev.0),
+                        unwrap(candidate.1, prev.1),
+                        unwrap(candidate.2, prev.2),
+                    );
+                    let dist = (candidate - prev).sqr_magnitude();
+                    if dist < best_dist {
+                        best = candidate;
+                        best_dist = dist;
+                    }
+                }
+                return best;
+            }
+
+            // Bernardes & Viollet, "Quaternion to Euler angles conversion: A direct, general and computationally
+            // efficient method", run on the equivalent extrinsic sequence. Also reports gimbal lock.
+            fn to_euler_gimbal(self, order: $crate::quaternion::EulerOrder) -> ($vec, bool) {
+                let pi = <$typ as $crate::traits::Float>::PI;
+                let q = self.normalized();
+                let comp = |axis: usize| [q.1, q.2, q.3][axis];
+
+                let (k, j, i) = order.axes();
+                let proper = i == k;
+                let k = if proper { 3 - i - j } else { k };
+                let sign = ((i as i32 - j as i32) * (j as i32 - k as i32) * (k as i32 - i as i32) / 2) as $typ;
+
+                let (a, b, c, d) = if proper {
+                    (q.0, comp(i), comp(j), comp(k) * sign)
+                } else {
+                    (q.0 - comp(j), comp(i) + comp(k) * sign, comp(j) + q.0, comp(k) * sign - comp(i))
+                };
+
+                let mut second = 2.0 * (c * c + d * d).sqrt().atan2((a * a + b * b).sqrt());
+                let half_sum = b.atan2(a);
+                let half_diff = d.atan2(c);
+
+                let eps = <$typ>::EPSILON.sqrt();
+                let gimbal = second.abs() <= eps || (second - pi).abs() <= eps;
+                let (mut first, mut third) = if second.abs() <= eps {
+                    (0.0, 2.0 * half_sum)
+                } else if (second - pi).abs() <= eps {
+                    (0.0, 2.0 * half_diff)
+                } else {
+                    (half_sum - half_diff, half_sum + half_diff)
+                };
+
+                if !proper {
+                    third *= sign;
+                    second -= pi * 0.5;
+                }
+
+                let wrap = |angle: $typ| if angle > pi { angle - 2.0 * pi } else if angle < -pi { angle + 2.0 * pi } else { angle };
+                first = wrap(first);
+                third = wrap(third);
+
+                // the extrinsic angles come out in reverse order
+                return ($vec(third, second, first), gimbal);
+            }
+
+            fn axis_rotator(axis: usize, angle: $typ) -> Self {
+                let (sin, cos) = (angle * 0.5).sin_cos();
+                return match axis {
+                    0 => Self(cos, sin, 0.0, 0.0),
+                    1 => Self(cos, 0.0, sin, 0.0),
+                    _ => Self(cos, 0.0, 0.0, sin),
+                };
+            }
+
             pub const fn from_split(real: $typ, im: $vec) -> Self {
                 return Self(real, im.0, im.1, im.2);
             }
@@ -55,7 +266,7 @@ macro_rules! quat_gen {
             }
 
             pub fn normalize(&mut self) {
-                *self = *self / self.norm();
+                *self = self.normalized();
             }
             
             pub fn normalized(self) -> Self {
@@ -90,6 +301,320 @@ macro_rules! quat_gen {
             pub fn forw(&self) -> $vec {
                 return self.basis().2;
             }
+
+            /// Returns the shortest rotation that takes the direction `from` to the direction `to`.
+            /// When they point in opposite directions any perpendicular axis is used.
+            pub fn from_to(from: $vec, to: $vec) -> Self {
+                let from = from.normalized();
+                let to = to.normalized();
+                let cos = from.dot(to);
+
+                if cos < -1.0 + <$typ>::EPSILON.sqrt() {
+                    let mut axis = $vec::RIGHT.cross(from);
+                    if axis.sqr_magnitude() < <$typ>::EPSILON.sqrt() {
+                        axis = $vec::UP.cross(from);
+                    }
+                    return Self::from_split(0.0, axis.normalized());
+                }
+
+                return Self::from_split(1.0 + cos, from.cross(to)).normalized();
+            }
+
+            /// Builds the rotation whose `forw` points along `forward` and whose up vector is as close
+            /// as possible to `up`, following the same axes as `basis`.
+            pub fn look_rotation(forward: $vec, up: $vec) -> Self {
+                let z = forward.normalized();
+                let mut x = up.cross(z);
+                if x.sqr_magnitude() < <$typ>::EPSILON.sqrt() {
+                    // up is parallel to forward, any up will do
+                    x = if z.1.abs() < 0.9 { $vec::UP.cross(z) } else { $vec::FORW.cross(z) };
+                }
+                let x = x.normalized();
+
+                return Self::from_basis(x, z.cross(x), z);
+            }
+
+            /// Rotates towards `target` by at most `max_angle` radians, reaching it exactly once close enough.
+            pub fn rotate_towards(self, target: Self, max_angle: $typ) -> Self {
+                let angle = self.angle_to(target);
+                if angle <= max_angle {
+                    return target;
+                }
+
+                return self.slerp(target, max_angle / angle);
+            }
+
+            /// Splits the rotation into a twist around `axis` and a swing perpendicular to it,
+            /// returned as `(swing, twist)` so that `self == swing * twist`.
+            pub fn swing_twist(self, axis: $vec) -> (Self, Self) {
+                let axis = axis.normalized();
+                let proj = axis * self.vector().dot(axis);
+                let twist = Self::from_split(self.0, proj);
+
+                // swing of exactly 180 degrees, the twist is undefined
+                if twist.dot(twist) < <$typ>::EPSILON {
+                    return (self, Self::IDENT);
+                }
+
+                let twist = twist.normalized();
+                return (self * twist.conjugate(), twist);
+            }
+
+            /// Limits the swing away from `axis` to `max_angle` radians, keeping the twist around it.
+            pub fn clamp_swing(self, axis: $vec, max_angle: $typ) -> Self {
+                let (swing, twist) = self.swing_twist(axis);
+                let swing_aa = swing.to_axis_angle();
+                if swing_aa.magnitude() <= max_angle {
+                    return self;
+                }
+
+                return Self::rotator(swing_aa.normalized() * max_angle) * twist;
+            }
+
+            /// Limits the signed twist around `axis` to `[min, max]` radians, keeping the swing.
+            pub fn clamp_twist(self, axis: $vec, min: $typ, max: $typ) -> Self {
+                let axis = axis.normalized();
+                let (swing, twist) = self.swing_twist(axis);
+                let angle = twist.to_axis_angle().dot(axis);
+                if angle >= min && angle <= max {
+                    return self;
+                }
+
+                return swing * Self::rotator(axis * angle.clamp(min, max));
+            }
+
+            /// Averages rotations given as `(rotation, weight)` pairs. `q` and `-q` are treated as the
+            /// same rotation. Returns `IDENT` for an empty slice.
+            pub fn weighted_average(samples: &[(Self, $typ)], mode: $crate::quaternion::QuatAverageMode) -> Self {
+                let Some(&(first, _)) = samples.first() else {
+                    return Self::IDENT;
+                };
+
+                let res = match mode {
+                    $crate::quaternion::QuatAverageMode::NormalizedSum => {
+                        let mut sum = Self(0.0, 0.0, 0.0, 0.0);
+                        for &(q, weight) in samples {
+                            sum += if q.dot(first) < 0.0 { -q * weight } else { q * weight };
+                        }
+                        sum
+                    },
+                    $crate::quaternion::QuatAverageMode::Markley => {
+                        let mut m = [[0.0; 4]; 4];
+                        for &(q, weight) in samples {
+                            let q = q.normalized();
+                            let q = [q.0, q.1, q.2, q.3];
+                            for i in 0..4 {
+                                for j in 0..4 {
+                                    m[i][j] += weight * q[i] * q[j];
+                                }
+                            }
+                        }
+
+                        let v = Self::max_eigenvector(m);
+                        let v = Self(v[0], v[1], v[2], v[3]);
+                        if v.dot(first) < 0.0 { -v } else { v }
+                    },
+                };
+
+                if res.dot(res) == 0.0 {
+                    return Self::IDENT;
+                }
+                return res.normalized();
+            }
+
+            // cyclic Jacobi eigenvalue algorithm for symmetric 4x4 matrices
+            fn max_eigenvector(mut a: [[$typ; 4]; 4]) -> [$typ; 4] {
+                let mut v = [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]];
+
+                for _ in 0..32 {
+                    let mut off = 0.0;
+                    let mut diag = 0.0;
+                    for p in 0..4 {
+                        diag += a[p][p] * a[p][p];
+                        for q in (p + 1)..4 {
+                            off += a[p][q] * a[p][q];
+                        }
+                    }
+                    if off <= diag * <$typ>::EPSILON * <$typ>::EPSILON {
+                        break;
+                    }
+
+                    for p in 0..4 {
+                        for q in (p + 1)..4 {
+                            if a[p][q] == 0.0 {
+                                continue;
+                            }
+
+                            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                            let c = 1.0 / (t * t + 1.0).sqrt();
+                            let s = t * c;
+
+                            for k in 0..4 {
+                                let (kp, kq) = (a[k][p], a[k][q]);
+                                a[k][p] = c * kp - s * kq;
+                                a[k][q] = s * kp + c * kq;
+                            }
+                            for k in 0..4 {
+                                let (pk, qk) = (a[p][k], a[q][k]);
+                                a[p][k] = c * pk - s * qk;
+                                a[q][k] = s * pk + c * qk;
+                            }
+                            for row in v.iter_mut() {
+                                let (kp, kq) = (row[p], row[q]);
+                                row[p] = c * kp - s * kq;
+                                row[q] = s * kp + c * kq;
+                            }
+                        }
+                    }
+                }
+
+                let mut max = 0;
+                for i in 1..4 {
+                    if a[i][i] > a[max][max] {
+                        max = i;
+                    }
+                }
+                return [v[0][max], v[1][max], v[2][max], v[3][max]];
+            }
+
+            pub fn dot(self, other: Self) -> $typ {
+                return self.0 * other.0 + self.1 * other.1 + self.2 * other.2 + self.3 * other.3;
+            }
+
+            /// Returns the angle in radians of the rotation that takes `self` to `other`.
+            pub fn angle_to(self, other: Self) -> $typ {
+                return 2.0 * self.normalized().dot(other.normalized()).abs().min(1.0).acos();
+            }
+
+            /// Linearly interpolates and renormalizes, taking the shortest path.
+            /// Cheaper than `slerp` but does not have a constant angular velocity.
+            pub fn nlerp(self, other: Self, t: $typ) -> Self {
+                let other = if self.dot(other) < 0.0 { -other } else { other };
+                return (self * (1.0 - t) + other * t).normalized();
+            }
+
+            /// Spherical linear interpolation, taking the shortest path.
+            /// Falls back to `nlerp` when both rotations are almost identical.
+            pub fn slerp(self, other: Self, t: $typ) -> Self {
+                let mut cos = self.dot(other);
+                let mut other = other;
+                if cos < 0.0 {
+                    other = -other;
+                    cos = -cos;
+                }
+
+                if cos > 0.9995 {
+                    return self.nlerp(other, t);
+                }
+
+                let theta = cos.acos();
+                let inv_sin = 1.0 / theta.sin();
+                return self * (((1.0 - t) * theta).sin() * inv_sin) + other * ((t * theta).sin() * inv_sin);
+            }
+
+            /// Spherical cubic interpolation between `self` and `other`, using the control points
+            /// `a` and `b` obtained from `squad_control`.
+            pub fn squad(self, a: Self, b: Self, other: Self, t: $typ) -> Self {
+                let outer = self.slerp_long(other, t);
+                let inner = a.slerp_long(b, t);
+                return outer.slerp_long(inner, 2.0 * t * (1.0 - t));
+            }
+
+            /// Computes the `squad` control point for `cur`, given its neighbours in the spline.
+            pub fn squad_control(prev: Self, cur: Self, next: Self) -> Self {
+                let inv = cur.conjugate();
+                let prev = if cur.dot(prev) < 0.0 { -prev } else { prev };
+                let next = if cur.dot(next) < 0.0 { -next } else { next };
+
+                let log_prev = (inv * prev).ln();
+                let log_next = (inv * next).ln();
+                return (cur * ((log_prev + log_next) * -0.25).exp()).normalized();
+            }
+
+            // slerp without the shortest path correction, squad needs to follow the path it was given
+            fn slerp_long(self, other: Self, t: $typ) -> Self {
+                let cos = self.dot(other);
+                if cos.abs() > 0.9995 {
+                    return (self * (1.0 - t) + other * t).normalized();
+                }
+
+                let theta = cos.acos();
+                let inv_sin = 1.0 / theta.sin();
+                return self * (((1.0 - t) * theta).sin() * inv_sin) + other * ((t * theta).sin() * inv_sin);
+            }
+
+            /// Quaternion exponential. For a pure quaternion `(0, v)` this is the rotation of `2 * |v|` radians
+            /// around `v`, so `rotator(axis_angle)` is `Self::from(axis_angle * 0.5).exp()`.
+            pub fn exp(self) -> Self {
+                let v = self.vector();
+                let theta = v.magnitude();
+                let scale = self.0.exp();
+                if theta == 0.0 {
+                    return Self(scale, 0.0, 0.0, 0.0);
+                }
+
+                return Self::from_split(theta.cos(), v * (theta.sin() / theta)) * scale;
+            }
+
+            /// Quaternion logarithm, the inverse of `exp`. Unit quaternions give a pure quaternion
+            /// holding half the rotation's axis-angle.
+            pub fn ln(self) -> Self {
+                let v = self.vector();
+                let sin = v.magnitude();
+                let norm = self.norm();
+                if sin == 0.0 {
+                    return Self(norm.ln(), 0.0, 0.0, 0.0);
+                }
+
+                return Self::from_split(norm.ln(), v * (sin.atan2(self.0) / sin));
+            }
+
+            /// Raises the quaternion to a real power. For unit quaternions this scales the rotation angle by `t`.
+            pub fn powf(self, t: $typ) -> Self {
+                return (self.ln() * t).exp();
+            }
+
+            /// Returns the rotation as an axis scaled by its angle in radians, the inverse of `rotator`.
+            /// The angle is always in `[0, pi]`.
+            pub fn to_axis_angle(self) -> $vec {
+                let q = if self.0 < 0.0 { -self.normalized() } else { self.normalized() };
+                let v = q.vector();
+                let sin = v.magnitude();
+                if sin == 0.0 {
+                    return $vec::ZERO;
+                }
+
+                return v * (2.0 * sin.atan2(q.0) / sin);
+            }
+
+            /// Advances the orientation by a world space angular velocity (axis scaled by radians per second)
+            /// over `dt` seconds, keeping the result normalized.
+            pub fn integrate(self, angular_velocity: $vec, dt: $typ) -> Self {
+                return (Self::rotator(angular_velocity * dt) * self).normalized();
+            }
+
+            /// Returns the constant world space angular velocity that takes `from` to `to` in `dt` seconds,
+            /// so that `from.integrate(vel, dt)` gives back `to`.
+            pub fn angular_velocity_between(from: Self, to: Self, dt: $typ) -> $vec {
+                return (to * from.inverted().conjugate()).to_axis_angle() / dt;
+            }
+        }
+
+        impl std::ops::Neg for $ident {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                return Self(-self.0, -self.1, -self.2, -self.3);
+            }
+        }
+
+        impl std::ops::Sub for $ident {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                return Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2, self.3 - rhs.3);
+            }
         }
 
         impl std::ops::Add for $ident {
@@ -160,4 +685,302 @@ macro_rules! quat_gen {
             }
         }
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::assert_vec_eq;
+
+    fn assert_same_rotation(a: Quatd, b: Quatd) {
+        assert!(a.dot(b).abs() > 1.0 - 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    fn rotations() -> Vec<Quatd> {
+        return vec![
+            Quatd::IDENT,
+            Quatd::rotator(Vec3d(0.3, -1.2, 0.7)),
+            Quatd::rotator(Vec3d(0.0, 0.0, 3.0)),
+            Quatd::rotator(Vec3d(-2.0, 0.5, 0.1)),
+            Quatd::rotator(Vec3d(1.0, 1.0, 1.0).normalized() * 3.1),
+        ];
+    }
+
+    #[test]
+    fn slerp_endpoints_and_constant_speed() {
+        let a = Quatd::rotator(Vec3d(0.2, 0.1, 0.0));
+        let b = Quatd::rotator(Vec3d(-0.5, 1.3, 0.4));
+        assert_same_rotation(a.slerp(b, 0.0), a);
+        assert_same_rotation(a.slerp(b, 1.0), b);
+
+        let angle = a.angle_to(b);
+        for t in [0.25, 0.5, 0.75] {
+            assert!((a.angle_to(a.slerp(b, t)) - angle * t).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn slerp_takes_the_shortest_path() {
+        let a = Quatd::rotator(Vec3d(0.0, 0.4, 0.0));
+        let b = Quatd::rotator(Vec3d(0.0, -0.4, 0.0));
+        assert_same_rotation(a.slerp(-b, 0.5), Quatd::IDENT);
+        assert_same_rotation(a.nlerp(-b, 0.5), Quatd::IDENT);
+    }
+
+    #[test]
+    fn squad_passes_through_the_keys() {
+        let keys = rotations();
+        let a = Quatd::squad_control(keys[0], keys[1], keys[2]);
+        let b = Quatd::squad_control(keys[1], keys[2], keys[3]);
+        assert_same_rotation(keys[1].squad(a, b, keys[2], 0.0), keys[1]);
+        assert_same_rotation(keys[1].squad(a, b, keys[2], 1.0), keys[2]);
+    }
+
+    #[test]
+    fn rotation_matrix_round_trip() {
+        for q in rotations() {
+            assert_same_rotation(Quatd::from_rotation_matrix(&Mat3d::from_quat(q)), q);
+            assert_same_rotation(Quatd::from_rotation_matrix(&Mat4d::from_quat(q)), q);
+        }
+    }
+
+    #[test]
+    fn rotation_matrix_rotates_like_the_quaternion() {
+        let v = Vec3d(0.3, -2.0, 1.5);
+        for q in rotations() {
+            let rotated = Mat3d::from_quat(q).mul_vec(v);
+            assert_vec_eq(rotated, v.rotate(q));
+        }
+    }
+
+    #[test]
+    fn rotation_matrix_ignores_scale() {
+        let q = rotations()[3];
+        let mat = Mat4d::tf_matrix(Vec3d(1.0, 2.0, 3.0), q, Vec3d(2.0, 0.5, 3.0));
+        assert_same_rotation(Quatd::from_rotation_matrix(&mat), q);
+    }
+
+    const ORDERS: [EulerOrder; 12] = [
+        EulerOrder::XYZ, EulerOrder::XZY, EulerOrder::YXZ, EulerOrder::YZX, EulerOrder::ZXY, EulerOrder::ZYX,
+        EulerOrder::XYX, EulerOrder::XZX, EulerOrder::YXY, EulerOrder::YZY, EulerOrder::ZXZ, EulerOrder::ZYZ,
+    ];
+
+    #[test]
+    fn euler_round_trip_for_every_order() {
+        for order in ORDERS {
+            for q in rotations() {
+                assert_same_rotation(Quatd::from_euler(order, q.to_euler(order)), q);
+            }
+
+            // angles inside the documented ranges come back unchanged
+            let angles = if order.is_proper() { Vec3d(0.4, 1.1, -2.5) } else { Vec3d(0.4, -1.1, 2.5) };
+            assert_vec_eq(Quatd::from_euler(order, angles).to_euler(order), angles);
+        }
+    }
+
+    #[test]
+    fn euler_round_trip_in_gimbal_lock() {
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        for order in ORDERS {
+            let second = if order.is_proper() { [0.0, std::f64::consts::PI] } else { [half_pi, -half_pi] };
+            for second in second {
+                let q = Quatd::from_euler(order, Vec3d(0.7, second, -0.3));
+                let angles = q.to_euler(order);
+                assert_same_rotation(Quatd::from_euler(order, angles), q);
+                assert_eq!(angles.2, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn euler_closest_stays_near_the_previous_angles() {
+        let two_pi = 2.0 * std::f64::consts::PI;
+        for order in ORDERS {
+            let prev = Vec3d(3.0, 0.5, -3.0);
+            let q = Quatd::from_euler(order, prev + Vec3d(0.3, 0.0, -0.2));
+            let angles = q.to_euler_closest(order, prev);
+            assert_same_rotation(Quatd::from_euler(order, angles), q);
+            assert!((angles - prev).magnitude() < 0.5, "{:?} {:?}", order, angles);
+
+            let wrapped = q.to_euler_closest(order, prev + Vec3d(two_pi, 0.0, 0.0));
+            assert!((wrapped.0 - angles.0 - two_pi).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn from_to_maps_the_directions() {
+        let dirs = [Vec3d::RIGHT, Vec3d::UP, Vec3d::FORW, Vec3d(1.0, -2.0, 0.5).normalized(), Vec3d(-0.3, 0.1, -1.0).normalized()];
+        for from in dirs {
+            for to in dirs {
+                assert_vec_eq(from.rotate(Quatd::from_to(from, to)), to);
+                assert_vec_eq(from.rotate(Quatd::from_to(from, -to)), -to);
+            }
+        }
+
+        // scaled inputs are normalized first
+        assert_vec_eq((Vec3d::RIGHT * 3.0).rotate(Quatd::from_to(Vec3d::RIGHT * 3.0, Vec3d::UP * 0.5)), Vec3d::UP * 3.0);
+    }
+
+    #[test]
+    fn look_rotation_points_forw_along_forward() {
+        let forward = Vec3d(1.0, 0.5, -2.0);
+        let q = Quatd::look_rotation(forward, Vec3d::UP);
+        assert_vec_eq(q.forw(), forward.normalized());
+        let (right, up, _) = q.basis();
+        assert!(right.1.abs() < 1e-9, "right should stay horizontal: {:?}", right);
+        assert!(up.1 > 0.0);
+
+        assert_same_rotation(Quatd::look_rotation(Vec3d::FORW, Vec3d::UP), Quatd::IDENT);
+
+        // up parallel to forward still gives a valid rotation
+        let q = Quatd::look_rotation(Vec3d::UP, Vec3d::UP);
+        assert_vec_eq(q.forw(), Vec3d::UP);
+        assert!((q.norm() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rotate_towards_steps_by_max_angle() {
+        let from = Quatd::IDENT;
+        let to = Quatd::rotator(Vec3d(0.0, 1.0, 0.0));
+
+        let step = from.rotate_towards(to, 0.25);
+        assert!((from.angle_to(step) - 0.25).abs() < 1e-9);
+        assert!((step.angle_to(to) - 0.75).abs() < 1e-9);
+
+        assert_eq!(from.rotate_towards(to, 1.0), to);
+        assert_eq!(from.rotate_towards(to, 2.0), to);
+    }
+
+    #[test]
+    fn exp_and_ln_are_inverse() {
+        for q in rotations() {
+            assert_same_rotation(q.ln().exp(), q);
+            assert!(q.ln().0.abs() < 1e-9, "unit quaternions have a pure log");
+        }
+
+        let axis_angle = Vec3d(0.3, -0.4, 1.2);
+        assert_same_rotation(Quatd::from(axis_angle * 0.5).exp(), Quatd::rotator(axis_angle));
+    }
+
+    #[test]
+    fn powf_scales_the_angle() {
+        for q in rotations() {
+            let half = q.powf(0.5);
+            assert_same_rotation(half * half, q);
+            assert_same_rotation(q.powf(0.0), Quatd::IDENT);
+            assert_same_rotation(q.powf(1.0), q);
+        }
+    }
+
+    #[test]
+    fn to_axis_angle_inverts_rotator() {
+        for axis_angle in [Vec3d(0.3, -1.2, 0.7), Vec3d(0.0, 0.0, 3.0), Vec3d(-2.0, 0.5, 0.1)] {
+            assert_vec_eq(Quatd::rotator(axis_angle).to_axis_angle(), axis_angle);
+            assert_vec_eq((-Quatd::rotator(axis_angle)).to_axis_angle(), axis_angle);
+        }
+        assert_eq!(Quatd::IDENT.to_axis_angle(), Vec3d::ZERO);
+
+        // angles past pi come back as the shorter rotation the other way
+        let long = Vec3d(0.0, 4.0, 0.0);
+        assert_vec_eq(Quatd::rotator(long).to_axis_angle(), Vec3d(0.0, 4.0 - 2.0 * std::f64::consts::PI, 0.0));
+    }
+
+    #[test]
+    fn integrate_follows_angular_velocity_between() {
+        for from in rotations() {
+            for to in rotations() {
+                let vel = Quatd::angular_velocity_between(from, to, 0.5);
+                assert_same_rotation(from.integrate(vel, 0.5), to);
+            }
+        }
+
+        let vel = Vec3d(0.0, 2.0, 0.0);
+        let q = Quatd::IDENT.integrate(vel, 0.25);
+        assert_same_rotation(q, Quatd::rotator(Vec3d(0.0, 0.5, 0.0)));
+        assert_vec_eq(Quatd::angular_velocity_between(Quatd::IDENT, q, 0.25), vel);
+    }
+
+    #[test]
+    fn swing_twist_recombines() {
+        for axis in [Vec3d::UP, Vec3d(1.0, 2.0, -0.5).normalized()] {
+            for q in rotations() {
+                let (swing, twist) = q.swing_twist(axis);
+                assert_same_rotation(swing * twist, q);
+
+                // the twist rotates about the axis, the swing about an axis perpendicular to it
+                assert!(twist.vector().cross(axis).magnitude() < 1e-9, "{:?}", twist);
+                assert!(swing.vector().dot(axis).abs() < 1e-9, "{:?}", swing);
+            }
+        }
+
+        // a half turn away from the axis has no defined twist
+        let (swing, twist) = Quatd::rotator(Vec3d(std::f64::consts::PI, 0.0, 0.0)).swing_twist(Vec3d::UP);
+        assert_eq!(twist, Quatd::IDENT);
+        assert_same_rotation(swing, Quatd::rotator(Vec3d(std::f64::consts::PI, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn clamp_swing_limits_the_swing_only() {
+        let twist = Quatd::rotator(Vec3d(0.0, 0.4, 0.0));
+        let q = Quatd::rotator(Vec3d(1.2, 0.0, 0.0)) * twist;
+
+        let clamped = q.clamp_swing(Vec3d::UP, 0.5);
+        let (swing, clamped_twist) = clamped.swing_twist(Vec3d::UP);
+        assert!((swing.to_axis_angle().magnitude() - 0.5).abs() < 1e-9);
+        assert_same_rotation(clamped_twist, twist);
+
+        assert_eq!(q.clamp_swing(Vec3d::UP, 1.5), q);
+    }
+
+    #[test]
+    fn clamp_twist_limits_the_twist_only() {
+        let swing = Quatd::rotator(Vec3d(0.3, 0.0, 0.2));
+        let q = swing * Quatd::rotator(Vec3d(0.0, 1.0, 0.0));
+
+        let clamped = q.clamp_twist(Vec3d::UP, -0.5, 0.25);
+        let (clamped_swing, twist) = clamped.swing_twist(Vec3d::UP);
+        assert!((twist.to_axis_angle().dot(Vec3d::UP) - 0.25).abs() < 1e-9);
+        assert_same_rotation(clamped_swing, swing);
+
+        let clamped = (swing * Quatd::rotator(Vec3d(0.0, -1.0, 0.0))).clamp_twist(Vec3d::UP, -0.5, 0.25);
+        assert!((clamped.swing_twist(Vec3d::UP).1.to_axis_angle().dot(Vec3d::UP) + 0.5).abs() < 1e-9);
+
+        assert_eq!(q.clamp_twist(Vec3d::UP, -2.0, 2.0), q);
+    }
+
+    #[test]
+    fn weighted_average_modes() {
+        for mode in [QuatAverageMode::NormalizedSum, QuatAverageMode::Markley] {
+            assert_eq!(Quatd::weighted_average(&[], mode), Quatd::IDENT);
+
+            let q = Quatd::rotator(Vec3d(0.3, -1.2, 0.7));
+            assert_same_rotation(Quatd::weighted_average(&[(q, 2.0)], mode), q);
+
+            // opposite signs are the same rotation
+            assert_same_rotation(Quatd::weighted_average(&[(q, 1.0), (-q, 1.0)], mode), q);
+
+            // two rotations about one axis average to the midpoint, weights pull towards the heavier one
+            let a = Quatd::rotator(Vec3d(0.0, 0.2, 0.0));
+            let b = Quatd::rotator(Vec3d(0.0, 1.0, 0.0));
+            assert_same_rotation(Quatd::weighted_average(&[(a, 1.0), (b, 1.0)], mode), Quatd::rotator(Vec3d(0.0, 0.6, 0.0)));
+            let heavy = Quatd::weighted_average(&[(a, 3.0), (b, 1.0)], mode);
+            assert!(heavy.angle_to(a) < heavy.angle_to(b));
+            assert!((heavy.norm() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn markley_average_is_order_independent() {
+        let samples = [
+            (Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), 1.0),
+            (-Quatd::rotator(Vec3d(0.5, -1.0, 0.4)), 2.0),
+            (Quatd::rotator(Vec3d(0.1, -1.4, 0.9)), 0.5),
+        ];
+        let mut reversed = samples;
+        reversed.reverse();
+
+        let a = Quatd::weighted_average(&samples, QuatAverageMode::Markley);
+        let b = Quatd::weighted_average(&reversed, QuatAverageMode::Markley);
+        assert_same_rotation(a, b);
+    }
+}
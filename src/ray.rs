@@ -0,0 +1,416 @@
+// Solids (spheres, boxes, capsules, ...) are only hit from the outside, a ray starting inside one of them
+// doesn't report a hit. Only hits in front of the origin count.
+
+use crate::traits::Float;
+
+/// Slab test shared by the box and rect intersections, one slab per axis. Returns the entry distance and
+/// the axis of the entry face. Zero direction components are checked against the origin, `0 * inf` would
+/// give NaN.
+pub fn slabs<T: Float>(origin: &[T], dir: &[T], min: &[T], max: &[T]) -> Option<(T, usize)> {
+    let mut entry: Option<(T, usize)> = None;
+    let mut t_far: Option<T> = None;
+    for i in 0..origin.len() {
+        if dir[i] == T::ZERO {
+            if origin[i] < min[i] || origin[i] > max[i] {
+                return None;
+            }
+            continue;
+        }
+
+        let t1 = (min[i] - origin[i]) / dir[i];
+        let t2 = (max[i] - origin[i]) / dir[i];
+        let (near, far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+        if entry.is_none_or(|(t_near, _)| near > t_near) {
+            entry = Some((near, i));
+        }
+        if t_far.is_none_or(|t_far| far < t_far) {
+            t_far = Some(far);
+        }
+    }
+
+    let (t_near, axis) = entry?;
+    if t_far.is_some_and(|t_far| t_near > t_far) || t_near < T::ZERO {
+        return None;
+    }
+    return Some((t_near, axis));
+}
+
+#[macro_export]
+macro_rules! gen_ray3 {
+    ($ident:ident, $hit:ident, $vec3:ident, $typ:ty) => {
+        /// Half-line starting at `origin`, `dir` is expected to be normalized so distances along the ray
+        /// are in world units.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub origin: $vec3,
+            pub dir: $vec3,
+        }
+
+        /// Closest intersection of a ray, `distance` along the ray and the surface normal at that point.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $hit {
+            pub distance: $typ,
+            pub normal: $vec3,
+        }
+
+        impl $ident {
+            pub const fn new(origin: $vec3, dir: $vec3) -> Self {
+                return Self { origin, dir };
+            }
+
+            pub fn at(&self, t: $typ) -> $vec3 {
+                return self.origin + self.dir * t;
+            }
+
+            /// Intersects the plane `normal . p + dist = 0`, from either side. The returned normal faces the ray.
+            pub fn intersect_plane(&self, normal: $vec3, dist: $typ) -> Option<$hit> {
+                let denom = normal.dot(self.dir);
+                if denom == 0.0 {
+                    return None;
+                }
+
+                let t = -(normal.dot(self.origin) + dist) / denom;
+                if t < 0.0 {
+                    return None;
+                }
+                let normal = if denom < 0.0 { normal } else { -normal };
+                return Some($hit { distance: t, normal });
+            }
+
+            /// `dir` must be normalized, the quadratic assumes `dir . dir == 1`.
+            pub fn intersect_sphere(&self, center: $vec3, radius: $typ) -> Option<$hit> {
+                let offset = self.origin - center;
+                let b = offset.dot(self.dir);
+                let c = offset.sqr_magnitude() - radius * radius;
+                let disc = b * b - c;
+                if c < 0.0 || b > 0.0 || disc < 0.0 {
+                    return None;
+                }
+
+                let t = -b - disc.sqrt();
+                return Some($hit { distance: t, normal: (self.at(t) - center) / radius });
+            }
+
+            /// Slab test against the axis aligned box between `min` and `max`. Rays running along a face
+            /// count as hitting it.
+            pub fn intersect_aabb(&self, min: $vec3, max: $vec3) -> Option<$hit> {
+                let origin = [self.origin.0, self.origin.1, self.origin.2];
+                let dir = [self.dir.0, self.dir.1, self.dir.2];
+                let (min, max) = ([min.0, min.1, min.2], [max.0, max.1, max.2]);
+
+                let (t_near, axis) = $crate::ray::slabs(&origin, &dir, &min, &max)?;
+                let mut normal = [0.0; 3];
+                normal[axis] = -dir[axis].signum();
+                return Some($hit { distance: t_near, normal: $vec3(normal[0], normal[1], normal[2]) });
+            }
+
+            /// Möller–Trumbore intersection with the triangle `a`, `b`, `c`, from either side. The returned
+            /// normal faces the ray.
+            pub fn intersect_triangle(&self, a: $vec3, b: $vec3, c: $vec3) -> Option<$hit> {
+                let edge1 = b - a;
+                let edge2 = c - a;
+                let p = self.dir.cross(edge2);
+                let det = edge1.dot(p);
+                if det.abs() < <$typ>::EPSILON {
+                    return None;
+                }
+
+                let inv_det = 1.0 / det;
+                let s = self.origin - a;
+                let u = s.dot(p) * inv_det;
+                if !(0.0..=1.0).contains(&u) {
+                    return None;
+                }
+
+                let q = s.cross(edge1);
+                let v = self.dir.dot(q) * inv_det;
+                if v < 0.0 || u + v > 1.0 {
+                    return None;
+                }
+
+                let t = edge2.dot(q) * inv_det;
+                if t < 0.0 {
+                    return None;
+                }
+
+                let normal = edge1.cross(edge2).normalized();
+                let normal = if normal.dot(self.dir) < 0.0 { normal } else { -normal };
+                return Some($hit { distance: t, normal });
+            }
+
+            /// Intersects the capsule around the segment from `a` to `b`, `dir` must be normalized. A capsule
+            /// with `a == b` is a sphere.
+            pub fn intersect_capsule(&self, a: $vec3, b: $vec3, radius: $typ) -> Option<$hit> {
+                let axis = b - a;
+                let axis_sqr = axis.sqr_magnitude();
+                if axis_sqr == 0.0 {
+                    return self.intersect_sphere(a, radius);
+                }
+
+                let offset = self.origin - a;
+                let axis_dir = axis.dot(self.dir);
+                let axis_offset = axis.dot(offset);
+                let offset_sqr = offset.sqr_magnitude();
+                let closest = a + axis * (axis_offset / axis_sqr).clamp(0.0, 1.0);
+                if (self.origin - closest).sqr_magnitude() < radius * radius {
+                    return None;
+                }
+
+                // cylinder part
+                let qa = axis_sqr - axis_dir * axis_dir;
+                let qb = axis_sqr * offset.dot(self.dir) - axis_offset * axis_dir;
+                let qc = axis_sqr * offset_sqr - axis_offset * axis_offset - radius * radius * axis_sqr;
+                let disc = qb * qb - qa * qc;
+                if qa != 0.0 && disc >= 0.0 {
+                    let t = (-qb - disc.sqrt()) / qa;
+                    let along = axis_offset + t * axis_dir;
+                    if t >= 0.0 && along > 0.0 && along < axis_sqr {
+                        let point = self.at(t);
+                        let normal = (point - a - axis * (along / axis_sqr)) / radius;
+                        return Some($hit { distance: t, normal });
+                    }
+                }
+
+                // caps, the closer sphere is the first one hit
+                let first = self.intersect_sphere(a, radius);
+                let second = self.intersect_sphere(b, radius);
+                return match (first, second) {
+                    (Some(first), Some(second)) => Some(if first.distance <= second.distance { first } else { second }),
+                    (first, second) => first.or(second),
+                };
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! gen_ray2 {
+    ($ident:ident, $hit:ident, $vec2:ident, $rect:ident, $typ:ty) => {
+        /// 2D half-line starting at `origin`, `dir` is expected to be normalized so distances along the ray
+        /// are in world units.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub origin: $vec2,
+            pub dir: $vec2,
+        }
+
+        /// Closest intersection of a ray, `distance` along the ray and the surface normal at that point.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $hit {
+            pub distance: $typ,
+            pub normal: $vec2,
+        }
+
+        impl $ident {
+            pub const fn new(origin: $vec2, dir: $vec2) -> Self {
+                return Self { origin, dir };
+            }
+
+            pub fn at(&self, t: $typ) -> $vec2 {
+                return self.origin + self.dir * t;
+            }
+
+            /// Intersects the line `normal . p + dist = 0`, from either side. The returned normal faces the ray.
+            pub fn intersect_line(&self, normal: $vec2, dist: $typ) -> Option<$hit> {
+                let denom = normal.dot(self.dir);
+                if denom == 0.0 {
+                    return None;
+                }
+
+                let t = -(normal.dot(self.origin) + dist) / denom;
+                if t < 0.0 {
+                    return None;
+                }
+                let normal = if denom < 0.0 { normal } else { -normal };
+                return Some($hit { distance: t, normal });
+            }
+
+            /// `dir` must be normalized, the quadratic assumes `dir . dir == 1`.
+            pub fn intersect_circle(&self, center: $vec2, radius: $typ) -> Option<$hit> {
+                let offset = self.origin - center;
+                let b = offset.dot(self.dir);
+                let c = offset.sqr_magnitude() - radius * radius;
+                let disc = b * b - c;
+                if c < 0.0 || b > 0.0 || disc < 0.0 {
+                    return None;
+                }
+
+                let t = -b - disc.sqrt();
+                return Some($hit { distance: t, normal: (self.at(t) - center) / radius });
+            }
+
+            /// Slab test against the rect. Rays running along an edge count as hitting it.
+            pub fn intersect_rect(&self, rect: $rect) -> Option<$hit> {
+                let origin = [self.origin.0, self.origin.1];
+                let dir = [self.dir.0, self.dir.1];
+                let (min, max) = ([rect.start.0, rect.start.1], [rect.end.0, rect.end.1]);
+
+                let (t_near, axis) = $crate::ray::slabs(&origin, &dir, &min, &max)?;
+                let mut normal = [0.0; 2];
+                normal[axis] = -dir[axis].signum();
+                return Some($hit { distance: t_near, normal: $vec2(normal[0], normal[1]) });
+            }
+
+            /// Intersects the capsule around the segment from `a` to `b`, `dir` must be normalized. A capsule
+            /// with `a == b` is a circle.
+            pub fn intersect_capsule(&self, a: $vec2, b: $vec2, radius: $typ) -> Option<$hit> {
+                let axis = b - a;
+                let axis_sqr = axis.sqr_magnitude();
+                if axis_sqr == 0.0 {
+                    return self.intersect_circle(a, radius);
+                }
+
+                let offset = self.origin - a;
+                let axis_dir = axis.dot(self.dir);
+                let axis_offset = axis.dot(offset);
+                let offset_sqr = offset.sqr_magnitude();
+                let closest = a + axis * (axis_offset / axis_sqr).clamp(0.0, 1.0);
+                if (self.origin - closest).sqr_magnitude() < radius * radius {
+                    return None;
+                }
+
+                // rectangle part
+                let qa = axis_sqr - axis_dir * axis_dir;
+                let qb = axis_sqr * offset.dot(self.dir) - axis_offset * axis_dir;
+                let qc = axis_sqr * offset_sqr - axis_offset * axis_offset - radius * radius * axis_sqr;
+                let disc = qb * qb - qa * qc;
+                if qa != 0.0 && disc >= 0.0 {
+                    let t = (-qb - disc.sqrt()) / qa;
+                    let along = axis_offset + t * axis_dir;
+                    if t >= 0.0 && along > 0.0 && along < axis_sqr {
+                        let point = self.at(t);
+                        let normal = (point - a - axis * (along / axis_sqr)) / radius;
+                        return Some($hit { distance: t, normal });
+                    }
+                }
+
+                // caps, the closer circle is the first one hit
+                let first = self.intersect_circle(a, radius);
+                let second = self.intersect_circle(b, radius);
+                return match (first, second) {
+                    (Some(first), Some(second)) => Some(if first.distance <= second.distance { first } else { second }),
+                    (first, second) => first.or(second),
+                };
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn assert_hit(hit: Option<RayHit3d>, distance: f64, normal: Vec3d) {
+        let hit = hit.expect("expected a hit");
+        assert!((hit.distance - distance).abs() < 1e-9, "{:?}", hit);
+        assert!((hit.normal - normal).magnitude() < 1e-9, "{:?}", hit);
+    }
+
+    fn assert_hit2(hit: Option<RayHit2d>, distance: f64, normal: Vec2d) {
+        let hit = hit.expect("expected a hit");
+        assert!((hit.distance - distance).abs() < 1e-9, "{:?}", hit);
+        assert!((hit.normal - normal).magnitude() < 1e-9, "{:?}", hit);
+    }
+
+    #[test]
+    fn plane_hits_from_both_sides() {
+        let ray = Ray3d::new(Vec3d(0.0, 5.0, 0.0), -Vec3d::UP);
+        assert_hit(ray.intersect_plane(Vec3d::UP, -1.0), 4.0, Vec3d::UP);
+        assert_hit(ray.intersect_plane(-Vec3d::UP, 1.0), 4.0, Vec3d::UP);
+        assert_eq!(ray.intersect_plane(Vec3d::UP, -6.0), None);
+        assert_eq!(ray.intersect_plane(Vec3d::RIGHT, 0.0), None);
+    }
+
+    #[test]
+    fn sphere_hits_only_from_outside() {
+        let ray = Ray3d::new(Vec3d(0.0, 0.0, -10.0), Vec3d::FORW);
+        assert_hit(ray.intersect_sphere(Vec3d(0.0, 0.0, 2.0), 3.0), 9.0, -Vec3d::FORW);
+        assert_eq!(ray.intersect_sphere(Vec3d(0.0, 4.0, 2.0), 3.0), None);
+        assert_eq!(ray.intersect_sphere(Vec3d(0.0, 0.0, -20.0), 3.0), None);
+        assert_eq!(ray.intersect_sphere(Vec3d(0.0, 0.0, -10.0), 3.0), None);
+    }
+
+    #[test]
+    fn aabb_slabs() {
+        let (min, max) = (Vec3d::one(-1.0), Vec3d::ONE);
+        assert_hit(Ray3d::new(Vec3d(0.0, 0.0, -10.0), Vec3d::FORW).intersect_aabb(min, max), 9.0, -Vec3d::FORW);
+        assert_hit(Ray3d::new(Vec3d(5.0, 0.5, 0.0), -Vec3d::RIGHT).intersect_aabb(min, max), 4.0, Vec3d::RIGHT);
+
+        let diagonal = Ray3d::new(Vec3d(-3.0, -2.0, -2.5), Vec3d::ONE.normalized());
+        assert_hit(diagonal.intersect_aabb(min, max), 2.0 * 3.0_f64.sqrt(), -Vec3d::RIGHT);
+
+        assert_eq!(Ray3d::new(Vec3d(0.0, 3.0, -10.0), Vec3d::FORW).intersect_aabb(min, max), None);
+        assert_eq!(Ray3d::new(Vec3d(0.0, 0.0, 10.0), Vec3d::FORW).intersect_aabb(min, max), None);
+        assert_eq!(Ray3d::new(Vec3d::ZERO, Vec3d::FORW).intersect_aabb(min, max), None);
+    }
+
+    #[test]
+    fn aabb_ray_along_a_face() {
+        // the origin lies on the x slab planes, `0 * inf` used to give NaN and miss
+        let (min, max) = (Vec3d::one(-1.0), Vec3d::ONE);
+        assert_hit(Ray3d::new(Vec3d(1.0, 0.0, -10.0), Vec3d::FORW).intersect_aabb(min, max), 9.0, -Vec3d::FORW);
+        assert_hit(Ray3d::new(Vec3d(-1.0, 1.0, -10.0), Vec3d::FORW).intersect_aabb(min, max), 9.0, -Vec3d::FORW);
+        assert_eq!(Ray3d::new(Vec3d(1.0 + 1e-9, 0.0, -10.0), Vec3d::FORW).intersect_aabb(min, max), None);
+    }
+
+    #[test]
+    fn triangle_hits_from_both_sides() {
+        let (a, b, c) = (Vec3d(-1.0, -1.0, 2.0), Vec3d(1.0, -1.0, 2.0), Vec3d(0.0, 1.0, 2.0));
+        assert_hit(Ray3d::new(Vec3d::ZERO, Vec3d::FORW).intersect_triangle(a, b, c), 2.0, -Vec3d::FORW);
+        assert_hit(Ray3d::new(Vec3d(0.0, 0.0, 5.0), -Vec3d::FORW).intersect_triangle(a, b, c), 3.0, Vec3d::FORW);
+        assert_eq!(Ray3d::new(Vec3d(0.9, 0.9, 0.0), Vec3d::FORW).intersect_triangle(a, b, c), None);
+        assert_eq!(Ray3d::new(Vec3d::ZERO, Vec3d::RIGHT).intersect_triangle(a, b, c), None);
+    }
+
+    #[test]
+    fn capsule_hits_the_side_and_the_caps() {
+        let (a, b) = (Vec3d(0.0, -2.0, 0.0), Vec3d(0.0, 2.0, 0.0));
+        assert_hit(Ray3d::new(Vec3d(-5.0, 1.0, 0.0), Vec3d::RIGHT).intersect_capsule(a, b, 0.5), 4.5, -Vec3d::RIGHT);
+        assert_hit(Ray3d::new(Vec3d(0.0, 10.0, 0.0), -Vec3d::UP).intersect_capsule(a, b, 0.5), 7.5, Vec3d::UP);
+        assert_hit(Ray3d::new(Vec3d(0.0, -10.0, 0.0), Vec3d::UP).intersect_capsule(a, b, 0.5), 7.5, -Vec3d::UP);
+        assert_eq!(Ray3d::new(Vec3d(-5.0, 3.0, 0.0), Vec3d::RIGHT).intersect_capsule(a, b, 0.5), None);
+
+        // inside the cap spheres or the cylinder doesn't count
+        assert_eq!(Ray3d::new(Vec3d(0.0, 2.2, 0.0), Vec3d::UP).intersect_capsule(a, b, 0.5), None);
+        assert_eq!(Ray3d::new(Vec3d(0.2, 0.0, 0.0), Vec3d::RIGHT).intersect_capsule(a, b, 0.5), None);
+    }
+
+    #[test]
+    fn capsule_with_equal_ends_is_a_sphere() {
+        let ray = Ray3d::new(Vec3d(-5.0, 0.0, 0.0), Vec3d::RIGHT);
+        let point = Vec3d(1.0, 0.0, 0.0);
+        assert_hit(ray.intersect_capsule(point, point, 2.0), 4.0, -Vec3d::RIGHT);
+        assert_eq!(ray.intersect_capsule(point, point, 2.0), ray.intersect_sphere(point, 2.0));
+        assert_eq!(Ray3d::new(Vec3d(0.5, 0.0, 0.0), Vec3d::RIGHT).intersect_capsule(point, point, 2.0), None);
+
+        let ray = Ray2d::new(Vec2d(-5.0, 0.0), Vec2d::RIGHT);
+        assert_hit2(ray.intersect_capsule(Vec2d(1.0, 0.0), Vec2d(1.0, 0.0), 2.0), 4.0, -Vec2d::RIGHT);
+    }
+
+    #[test]
+    fn ray2_line_circle_and_capsule() {
+        let ray = Ray2d::new(Vec2d(-5.0, 0.0), Vec2d::RIGHT);
+        assert_hit2(ray.intersect_line(Vec2d::RIGHT, -1.0), 6.0, -Vec2d::RIGHT);
+        assert_eq!(ray.intersect_line(Vec2d::UP, 0.0), None);
+
+        assert_hit2(ray.intersect_circle(Vec2d(1.0, 0.0), 2.0), 4.0, -Vec2d::RIGHT);
+        assert_eq!(ray.intersect_circle(Vec2d(1.0, 3.0), 2.0), None);
+
+        let (a, b) = (Vec2d(0.0, -2.0), Vec2d(0.0, 2.0));
+        assert_hit2(ray.intersect_capsule(a, b, 0.5), 4.5, -Vec2d::RIGHT);
+        assert_hit2(Ray2d::new(Vec2d(0.0, 10.0), -Vec2d::UP).intersect_capsule(a, b, 0.5), 7.5, Vec2d::UP);
+    }
+
+    #[test]
+    fn ray2_rect_slabs() {
+        let rect = Rectd { start: Vec2d::one(-1.0), end: Vec2d::ONE };
+        assert_hit2(Ray2d::new(Vec2d(-5.0, 0.0), Vec2d::RIGHT).intersect_rect(rect), 4.0, -Vec2d::RIGHT);
+        assert_hit2(Ray2d::new(Vec2d(0.5, 5.0), -Vec2d::UP).intersect_rect(rect), 4.0, Vec2d::UP);
+        assert_eq!(Ray2d::new(Vec2d(-5.0, 2.0), Vec2d::RIGHT).intersect_rect(rect), None);
+        assert_eq!(Ray2d::new(Vec2d::ZERO, Vec2d::RIGHT).intersect_rect(rect), None);
+
+        // running along an edge
+        assert_hit2(Ray2d::new(Vec2d(-5.0, 1.0), Vec2d::RIGHT).intersect_rect(rect), 4.0, -Vec2d::RIGHT);
+        assert_hit2(Ray2d::new(Vec2d(-1.0, -5.0), Vec2d::UP).intersect_rect(rect), 4.0, -Vec2d::UP);
+    }
+}
@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! gen_rect {
     ($ident:ident, $vec:tt, $typ:ty, $two:literal) => {
-        #[derive(Debug, Clone, Copy)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
         pub struct $ident {
             pub start: $vec,
             pub end: $vec,
@@ -9,6 +9,21 @@ macro_rules! gen_rect {
     
         impl $ident {
             pub const IDENT: Self = Self { start: <$vec>::ZERO, end: <$vec>::ONE };
+
+            pub const fn new(start: $vec, end: $vec) -> Self {
+                return Self { start, end };
+            }
+
+            pub fn from_center_size(center: $vec, size: $vec) -> Self {
+                let start = center - size / $two;
+                return Self { start, end: start + size };
+            }
+
+            /// Smallest rect containing every point, `None` for an empty slice.
+            pub fn from_points(points: &[$vec]) -> Option<Self> {
+                let (start, end) = $crate::traits::bounding_box(points)?;
+                return Some(Self { start, end });
+            }
     
             pub fn up(&self) -> $typ {
                 return self.end.1;
@@ -59,6 +74,74 @@ macro_rules! gen_rect {
             pub fn center(&self) -> $vec {
                 return (self.start + self.end) / $two;
             }
+
+            pub fn area(&self) -> $typ {
+                let size = self.size();
+                return size.0 * size.1;
+            }
+
+            /// True if the rect has no area, including when `end` is before `start`.
+            pub fn is_empty(&self) -> bool {
+                return self.end.0 <= self.start.0 || self.end.1 <= self.start.1;
+            }
+
+            /// Reorders the corners so `start` is the minimum and `end` the maximum, for rects built from
+            /// two arbitrary corners (e.g. a drag selection).
+            pub fn normalized(self) -> Self {
+                return Self { start: self.start.min(self.end), end: self.start.max(self.end) };
+            }
+
+            /// True if the rects overlap or touch.
+            pub fn intersects(&self, other: &Self) -> bool {
+                return
+                    self.left() <= other.right() && self.right() >= other.left() &&
+                    self.down() <= other.up() && self.up() >= other.down();
+            }
+
+            /// Overlapping part of both rects, `None` if they don't touch. Rects that only share an edge
+            /// give that edge as an empty rect.
+            pub fn intersection(&self, other: &Self) -> Option<Self> {
+                if !self.intersects(other) {
+                    return None;
+                }
+                return Some(Self { start: self.start.max(other.start), end: self.end.min(other.end) });
+            }
+
+            /// Smallest rect containing both rects.
+            pub fn union(&self, other: &Self) -> Self {
+                return Self { start: self.start.min(other.start), end: self.end.max(other.end) };
+            }
+
+            /// Point of the rect closest to `pos`, `pos` itself if it is inside.
+            pub fn clamp_point(&self, pos: $vec) -> $vec {
+                return pos.clamp(self.start, self.end);
+            }
+
+            /// Splits into the parts left and right of the vertical line `at` units from the left edge.
+            pub fn split_horizontal(&self, at: $typ) -> (Self, Self) {
+                let x = self.left() + at;
+                return (
+                    Self { start: self.start, end: $vec(x, self.up()) },
+                    Self { start: $vec(x, self.down()), end: self.end },
+                );
+            }
+
+            /// Splits into the parts below and above the horizontal line `at` units from the bottom edge.
+            pub fn split_vertical(&self, at: $typ) -> (Self, Self) {
+                let y = self.down() + at;
+                return (
+                    Self { start: self.start, end: $vec(self.right(), y) },
+                    Self { start: $vec(self.left(), y), end: self.end },
+                );
+            }
+
+            /// Moves each edge inwards by the given amount, negative values grow the rect.
+            pub fn inset(self, left: $typ, right: $typ, top: $typ, bottom: $typ) -> Self {
+                return Self {
+                    start: self.start + $vec(left, bottom),
+                    end: self.end - $vec(right, top),
+                };
+            }
         }
     };
 }
@@ -67,9 +150,345 @@ macro_rules! gen_rect {
 macro_rules! float_rect_impl {
     ($ident:ident, $vec:ident) => {
         impl $ident {
+            /// Maps `pos` from unit space, `(0, 0)` being `start` and `(1, 1)` `end`, into the rect.
             pub fn sample(&self, pos: $vec) -> $vec {
                 return self.start.scale($vec::ONE - pos) + self.end.scale(pos);
             }
+
+            /// Maps `pos` from the rect into unit space, the inverse of `sample`.
+            pub fn normalize(&self, pos: $vec) -> $vec {
+                return (pos - self.start).inv_scale(self.size());
+            }
+
+            /// Maps `pos` from this rect to the same relative position in `other`.
+            pub fn map_to(&self, other: &Self, pos: $vec) -> $vec {
+                return other.sample(self.normalize(pos));
+            }
+
+            /// Width divided by height.
+            pub fn aspect_ratio(&self) -> <$vec as $crate::traits::Vector>::Scalar {
+                let size = self.size();
+                return size.0 / size.1;
+            }
+
+            /// Biggest centered part of the rect with the given aspect ratio (width / height).
+            pub fn letterbox(&self, aspect_ratio: <$vec as $crate::traits::Vector>::Scalar) -> Self {
+                let size = self.size();
+                let size = if size.0 > size.1 * aspect_ratio {
+                    $vec(size.1 * aspect_ratio, size.1)
+                } else {
+                    $vec(size.0, size.0 / aspect_ratio)
+                };
+                return Self::from_center_size(self.center(), size);
+            }
         }
     };
-}
\ No newline at end of file
+}
+
+/// Cell helpers for integer rects, which are treated as half-open here: `start` is the first cell and `end`
+/// the first cell past the rect, so `size` is the number of cells and `area` the cell count. The `gen_rect`
+/// methods like `contains` keep including the `end` edges.
+#[macro_export]
+macro_rules! int_rect_impl {
+    ($ident:ident, $vec:ident, $typ:ty) => {
+        impl $ident {
+            /// Smallest rect containing every cell, `None` for an empty slice.
+            pub fn from_cells(cells: &[$vec]) -> Option<Self> {
+                let (start, last) = $crate::traits::bounding_box(cells)?;
+                return Some(Self { start, end: last + $vec::ONE });
+            }
+
+            /// True if `cell` is one of the rect's cells, which excludes the `end` edges.
+            pub fn contains_cell(&self, cell: $vec) -> bool {
+                return
+                    cell.0 >= self.left() && cell.0 < self.right() &&
+                    cell.1 >= self.down() && cell.1 < self.up();
+            }
+
+            /// True if the rects share at least one cell.
+            pub fn intersects_cells(&self, other: &Self) -> bool {
+                return
+                    self.left() < other.right() && self.right() > other.left() &&
+                    self.down() < other.up() && self.up() > other.down();
+            }
+
+            /// Cells shared by both rects, `None` if there are none.
+            pub fn cell_intersection(&self, other: &Self) -> Option<Self> {
+                let res = Self { start: self.start.max(other.start), end: self.end.min(other.end) };
+                if res.is_empty() {
+                    return None;
+                }
+                return Some(res);
+            }
+
+            /// Cell of the rect closest to `pos`, `pos` itself if it is inside. An empty rect has no cells
+            /// and gives `start`.
+            pub fn clamp_cell(&self, pos: $vec) -> $vec {
+                if self.is_empty() {
+                    return self.start;
+                }
+                return pos.clamp(self.start, self.end - $vec::ONE);
+            }
+
+            /// Iterates every cell in row-major order, x first.
+            pub fn cells(self) -> impl Iterator<Item = $vec> {
+                return self.rows().flat_map(|(y, xs)| xs.map(move |x| $vec(x, y)));
+            }
+
+            /// Iterates the rows as `(y, x range)` spans.
+            pub fn rows(self) -> impl Iterator<Item = ($typ, std::ops::Range<$typ>)> {
+                return (self.start.1..self.end.1).map(move |y| (y, self.start.0..self.end.0));
+            }
+
+            /// Clips the rect to a grid of `size` cells starting at zero, `None` if nothing is left.
+            pub fn clip_to_grid(&self, size: $vec) -> Option<Self> {
+                let clipped = Self { start: self.start.max($vec::ZERO), end: self.end.min(size) };
+                if clipped.is_empty() {
+                    return None;
+                }
+                return Some(clipped);
+            }
+
+            /// Splits the rect into tiles of `tile_size` cells starting at `start`, in row-major order.
+            /// The last tiles of each row and column are cut to fit.
+            pub fn tiles(self, tile_size: $vec) -> impl Iterator<Item = Self> {
+                assert!(tile_size.0 > 0 && tile_size.1 > 0, "Tile size must be positive. (Was {:?})", tile_size);
+
+                return (self.start.1..self.end.1).step_by(tile_size.1 as usize).flat_map(move |y| {
+                    (self.start.0..self.end.0).step_by(tile_size.0 as usize).map(move |x| {
+                        let start = $vec(x, y);
+                        return Self { start, end: (start + tile_size).min(self.end) };
+                    })
+                });
+            }
+
+            /// Iterates the chunks of a world-wide grid of `chunk_size` cells (with chunk `(0, 0)` starting at
+            /// zero) that overlap the rect, in row-major order, yielding each chunk coordinate with the part of
+            /// the rect inside that chunk.
+            pub fn chunks(self, chunk_size: $vec) -> impl Iterator<Item = ($vec, Self)> {
+                assert!(chunk_size.0 > 0 && chunk_size.1 > 0, "Chunk size must be positive. (Was {:?})", chunk_size);
+
+                // chunk coordinates, rounding the end up so it stays exclusive
+                let (start, end) = if self.is_empty() {
+                    ($vec::ZERO, $vec::ZERO)
+                } else {
+                    let end = self.end + chunk_size - $vec::ONE;
+                    (
+                        $vec(self.start.0.div_euclid(chunk_size.0), self.start.1.div_euclid(chunk_size.1)),
+                        $vec(end.0.div_euclid(chunk_size.0), end.1.div_euclid(chunk_size.1)),
+                    )
+                };
+
+                return Self { start, end }.cells().map(move |chunk| {
+                    let start = chunk.scale(chunk_size);
+                    let part = Self {
+                        start: start.max(self.start),
+                        end: (start + chunk_size).min(self.end),
+                    };
+                    return (chunk, part);
+                });
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn float_rect_set_operations() {
+        let a = Rectd::new(Vec2d(0.0, 0.0), Vec2d(2.0, 2.0));
+        let b = Rectd::new(Vec2d(1.0, -1.0), Vec2d(3.0, 1.0));
+        assert_eq!(a.union(&b), Rectd::new(Vec2d(0.0, -1.0), Vec2d(3.0, 2.0)));
+        assert_eq!(a.intersection(&b), Some(Rectd::new(Vec2d(1.0, 0.0), Vec2d(2.0, 1.0))));
+
+        // closed rects, a shared edge is an empty intersection
+        let touching = Rectd::new(Vec2d(2.0, 0.0), Vec2d(4.0, 2.0));
+        assert!(a.intersects(&touching));
+        assert!(a.intersection(&touching).unwrap().is_empty());
+        assert!(a.contains(Vec2d(2.0, 2.0)));
+
+        let apart = Rectd::new(Vec2d(2.5, 0.0), Vec2d(4.0, 2.0));
+        assert!(!a.intersects(&apart));
+        assert_eq!(a.intersection(&apart), None);
+    }
+
+    #[test]
+    fn float_rect_from_points_and_clamp() {
+        let points = [Vec2d(1.0, -2.0), Vec2d(-3.0, 4.0)];
+        let rect = Rectd::from_points(&points).unwrap();
+        assert_eq!(rect, Rectd::new(Vec2d(-3.0, -2.0), Vec2d(1.0, 4.0)));
+        assert!(points.iter().all(|&p| rect.contains(p)));
+        assert_eq!(Rectd::from_points(&[]), None);
+
+        assert_eq!(rect.clamp_point(Vec2d(5.0, 0.0)), Vec2d(1.0, 0.0));
+        assert_eq!(rect.clamp_point(Vec2d(0.0, 0.0)), Vec2d(0.0, 0.0));
+    }
+
+    #[test]
+    fn normalized_and_is_empty() {
+        let dragged = Rectd::new(Vec2d(3.0, 1.0), Vec2d(1.0, 4.0));
+        assert!(dragged.is_empty());
+        let rect = dragged.normalized();
+        assert_eq!(rect, Rectd::new(Vec2d(1.0, 1.0), Vec2d(3.0, 4.0)));
+        assert!(!rect.is_empty());
+        assert!(Rectd::new(Vec2d::ZERO, Vec2d(2.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn splits_cover_the_rect() {
+        let rect = Rectd::new(Vec2d(1.0, 2.0), Vec2d(5.0, 8.0));
+
+        let (left, right) = rect.split_horizontal(1.5);
+        assert_eq!(left, Rectd::new(Vec2d(1.0, 2.0), Vec2d(2.5, 8.0)));
+        assert_eq!(right, Rectd::new(Vec2d(2.5, 2.0), Vec2d(5.0, 8.0)));
+        assert_eq!(left.area() + right.area(), rect.area());
+
+        let (bottom, top) = rect.split_vertical(2.0);
+        assert_eq!(bottom, Rectd::new(Vec2d(1.0, 2.0), Vec2d(5.0, 4.0)));
+        assert_eq!(top, Rectd::new(Vec2d(1.0, 4.0), Vec2d(5.0, 8.0)));
+        assert_eq!(bottom.union(&top), rect);
+    }
+
+    #[test]
+    fn inset_moves_each_edge() {
+        let rect = Rectd::new(Vec2d(0.0, 0.0), Vec2d(10.0, 8.0));
+        let inner = rect.inset(1.0, 2.0, 3.0, 0.5);
+        assert_eq!((inner.left(), inner.right(), inner.up(), inner.down()), (1.0, 8.0, 5.0, 0.5));
+        assert_eq!(inner.inset(-1.0, -2.0, -3.0, -0.5), rect);
+        assert_eq!(rect.expand(1.0), rect.inset(-1.0, -1.0, -1.0, -1.0));
+    }
+
+    #[test]
+    fn sample_and_normalize_are_inverse() {
+        let rect = Rectd::new(Vec2d(1.0, 2.0), Vec2d(5.0, 8.0));
+        assert_eq!(rect.sample(Vec2d::ZERO), rect.start);
+        assert_eq!(rect.sample(Vec2d::ONE), rect.end);
+        assert_eq!(rect.sample(Vec2d(0.5, 0.5)), rect.center());
+
+        for pos in [Vec2d(0.25, 0.75), Vec2d(-1.0, 2.0)] {
+            assert!((rect.normalize(rect.sample(pos)) - pos).magnitude() < 1e-12);
+        }
+
+        let other = Rectd::new(Vec2d(-1.0, -1.0), Vec2d(1.0, 1.0));
+        assert_eq!(rect.map_to(&other, Vec2d(3.0, 5.0)), Vec2d(0.0, 0.0));
+        assert_eq!(rect.map_to(&other, rect.end), other.end);
+    }
+
+    #[test]
+    fn letterbox_keeps_the_aspect_ratio() {
+        let rect = Rectd::new(Vec2d(0.0, 0.0), Vec2d(16.0, 9.0));
+        assert_eq!(rect.aspect_ratio(), 16.0 / 9.0);
+
+        let pillar = rect.letterbox(1.0);
+        assert_eq!(pillar, Rectd::new(Vec2d(3.5, 0.0), Vec2d(12.5, 9.0)));
+
+        let letter = rect.letterbox(4.0);
+        assert_eq!(letter, Rectd::new(Vec2d(0.0, 2.5), Vec2d(16.0, 6.5)));
+        assert_eq!(letter.aspect_ratio(), 4.0);
+        assert_eq!(letter.center(), rect.center());
+    }
+
+    #[test]
+    fn int_rect_gen_methods_stay_closed() {
+        let rect = Recti::new(Vec2i(0, 0), Vec2i(2, 2));
+        assert!(rect.contains(Vec2i(2, 2)));
+        assert!(rect.contains(Vec2i(2, 1)));
+        assert!(!rect.contains(Vec2i(3, 1)));
+        assert!(Rectu::new(Vec2u(1, 1), Vec2u(3, 4)).contains(Vec2u(3, 4)));
+
+        let touching = Recti::new(Vec2i(2, 0), Vec2i(4, 2));
+        assert!(rect.intersects(&touching));
+        assert_eq!(rect.intersection(&touching), Some(Recti::new(Vec2i(2, 0), Vec2i(2, 2))));
+        assert_eq!(rect.clamp_point(Vec2i(5, -3)), Vec2i(2, 0));
+        assert_eq!(Recti::from_points(&[Vec2i(3, -2)]), Some(Recti::new(Vec2i(3, -2), Vec2i(3, -2))));
+    }
+
+    #[test]
+    fn int_rect_cells_are_half_open() {
+        // a single point is a single cell
+        let cell = Recti::from_cells(&[Vec2i(3, -2)]).unwrap();
+        assert_eq!(cell, Recti::new(Vec2i(3, -2), Vec2i(4, -1)));
+        assert_eq!(cell.area(), 1);
+        assert!(cell.contains_cell(Vec2i(3, -2)));
+        assert!(!cell.contains_cell(Vec2i(4, -2)));
+        assert_eq!(cell.cells().collect::<Vec<_>>(), vec![Vec2i(3, -2)]);
+        assert_eq!(Recti::from_cells(&[]), None);
+
+        let cells = [Vec2i(0, 5), Vec2i(4, 1), Vec2i(2, 2)];
+        let rect = Recti::from_cells(&cells).unwrap();
+        assert!(cells.iter().all(|&c| rect.contains_cell(c)));
+        assert_eq!(rect.area() as usize, rect.cells().count());
+
+        // rects sharing only an edge have no cell in common
+        let a = Recti::new(Vec2i(0, 0), Vec2i(2, 2));
+        let b = Recti::new(Vec2i(2, 0), Vec2i(4, 2));
+        assert!(!a.intersects_cells(&b));
+        assert_eq!(a.cell_intersection(&b), None);
+
+        let c = Recti::new(Vec2i(1, 1), Vec2i(4, 4));
+        assert!(a.intersects_cells(&c));
+        assert_eq!(a.cell_intersection(&c), Some(Recti::new(Vec2i(1, 1), Vec2i(2, 2))));
+
+        assert_eq!(a.clamp_cell(Vec2i(5, -3)), Vec2i(1, 0));
+        assert!(a.contains_cell(a.clamp_cell(Vec2i(5, 5))));
+    }
+
+    #[test]
+    fn int_rect_clamp_cell_of_an_empty_rect_is_the_start() {
+        assert_eq!(Recti::new(Vec2i(2, 2), Vec2i(2, 5)).clamp_cell(Vec2i::ZERO), Vec2i(2, 2));
+        assert_eq!(Rectu::new(Vec2u(4, 1), Vec2u(3, 3)).clamp_cell(Vec2u(9, 9)), Vec2u(4, 1));
+    }
+
+    #[test]
+    fn int_rect_cells_and_rows() {
+        let rect = Recti::new(Vec2i(1, -1), Vec2i(3, 1));
+        assert_eq!(rect.cells().collect::<Vec<_>>(), vec![Vec2i(1, -1), Vec2i(2, -1), Vec2i(1, 0), Vec2i(2, 0)]);
+        assert_eq!(rect.rows().collect::<Vec<_>>(), vec![(-1, 1..3), (0, 1..3)]);
+        assert_eq!(Recti::new(Vec2i(1, 1), Vec2i(1, 5)).cells().count(), 0);
+
+        let rect = Rectu::new(Vec2u(2, 3), Vec2u(4, 4));
+        assert_eq!(rect.cells().collect::<Vec<_>>(), vec![Vec2u(2, 3), Vec2u(3, 3)]);
+    }
+
+    #[test]
+    fn int_rect_clip_to_grid() {
+        let grid = Vec2i(10, 8);
+        assert_eq!(Recti::new(Vec2i(-2, 3), Vec2i(4, 12)).clip_to_grid(grid), Some(Recti::new(Vec2i(0, 3), Vec2i(4, 8))));
+        assert_eq!(Recti::new(Vec2i(10, 0), Vec2i(12, 2)).clip_to_grid(grid), None);
+        assert_eq!(Recti::new(Vec2i(-3, 0), Vec2i(0, 2)).clip_to_grid(grid), None);
+    }
+
+    #[test]
+    fn int_rect_tiles_cover_every_cell_once() {
+        let rect = Recti::new(Vec2i(1, 2), Vec2i(8, 7));
+        let tiles = rect.tiles(Vec2i(3, 2)).collect::<Vec<_>>();
+        assert_eq!(tiles.len(), 9);
+        assert_eq!(tiles[0], Recti::new(Vec2i(1, 2), Vec2i(4, 4)));
+        assert_eq!(tiles[2], Recti::new(Vec2i(7, 2), Vec2i(8, 4)));
+        assert_eq!(tiles[8], Recti::new(Vec2i(7, 6), Vec2i(8, 7)));
+
+        let mut cells = tiles.iter().flat_map(|tile| tile.cells()).collect::<Vec<_>>();
+        cells.sort_by_key(|cell| (cell.1, cell.0));
+        assert_eq!(cells, rect.cells().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn int_rect_chunks_split_along_the_world_grid() {
+        let rect = Recti::new(Vec2i(-3, 2), Vec2i(5, 4));
+        let chunks = rect.chunks(Vec2i(4, 4)).collect::<Vec<_>>();
+        assert_eq!(chunks, vec![
+            (Vec2i(-1, 0), Recti::new(Vec2i(-3, 2), Vec2i(0, 4))),
+            (Vec2i(0, 0), Recti::new(Vec2i(0, 2), Vec2i(4, 4))),
+            (Vec2i(1, 0), Recti::new(Vec2i(4, 2), Vec2i(5, 4))),
+        ]);
+
+        // an end on a chunk border doesn't reach into the next chunk
+        assert_eq!(Recti::new(Vec2i(0, 0), Vec2i(4, 4)).chunks(Vec2i(4, 4)).count(), 1);
+        assert_eq!(Recti::new(Vec2i(2, 2), Vec2i(2, 4)).chunks(Vec2i(4, 4)).count(), 0);
+
+        let total = rect.chunks(Vec2i(3, 3)).map(|(_, part)| part.area()).sum::<i32>();
+        assert_eq!(total, rect.area());
+    }
+}
@@ -0,0 +1,502 @@
+// The minimal enclosing constructions are Welzl's algorithm written as nested loops, each level keeping
+// one more point on the boundary. Its expected linear time needs the points in random order, sorted or
+// grid-like input can make it quadratic, shuffle those first.
+
+#[macro_export]
+macro_rules! gen_sphere {
+    ($ident:ident, $vec3:ident, $mat4:ident, $typ:ty) => {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub center: $vec3,
+            pub radius: $typ,
+        }
+
+        impl $ident {
+            pub const fn new(center: $vec3, radius: $typ) -> Self {
+                return Self { center, radius };
+            }
+
+            pub fn contains(&self, point: $vec3) -> bool {
+                return (point - self.center).sqr_magnitude() <= self.radius * self.radius;
+            }
+
+            pub fn contains_sphere(&self, other: &Self) -> bool {
+                return self.center.dist_to(other.center) + other.radius <= self.radius;
+            }
+
+            pub fn intersects(&self, other: &Self) -> bool {
+                let radius = self.radius + other.radius;
+                return (other.center - self.center).sqr_magnitude() <= radius * radius;
+            }
+
+            /// Smallest sphere containing both spheres.
+            pub fn merge(&self, other: &Self) -> Self {
+                let dist = self.center.dist_to(other.center);
+                if dist + other.radius <= self.radius {
+                    return *self;
+                }
+                if dist + self.radius <= other.radius {
+                    return *other;
+                }
+
+                let radius = (dist + self.radius + other.radius) * 0.5;
+                let center = self.center + (other.center - self.center) * ((radius - self.radius) / dist);
+                return Self { center, radius };
+            }
+
+            /// Transforms the sphere by an affine matrix. With a non-uniform scale the result is the sphere
+            /// around the resulting ellipsoid, scaled by the largest axis.
+            pub fn transform(&self, mat: &$mat4) -> Self {
+                let center = $vec3(
+                    mat[(0, 0)] * self.center.0 + mat[(0, 1)] * self.center.1 + mat[(0, 2)] * self.center.2 + mat[(0, 3)],
+                    mat[(1, 0)] * self.center.0 + mat[(1, 1)] * self.center.1 + mat[(1, 2)] * self.center.2 + mat[(1, 3)],
+                    mat[(2, 0)] * self.center.0 + mat[(2, 1)] * self.center.1 + mat[(2, 2)] * self.center.2 + mat[(2, 3)],
+                );
+                let scale = (0..3)
+                    .map(|col| $vec3(mat[(0, col)], mat[(1, col)], mat[(2, col)]).magnitude())
+                    .fold(0.0, <$typ>::max);
+
+                return Self { center, radius: self.radius * scale };
+            }
+
+            /// Fast approximation of the enclosing sphere (Ritter), usually a few percent bigger than the
+            /// minimal one. `None` for an empty slice.
+            pub fn ritter(points: &[$vec3]) -> Option<Self> {
+                let first = *points.first()?;
+                let farthest = |from: $vec3| -> $vec3 {
+                    return points.iter().copied().fold(from, |best, p| {
+                        if p.sqr_dist_to(from) > best.sqr_dist_to(from) { p } else { best }
+                    });
+                };
+
+                let a = farthest(first);
+                let b = farthest(a);
+                let mut res = Self::from2(a, b);
+                for &point in points {
+                    let dist = point.dist_to(res.center);
+                    if dist > res.radius {
+                        let radius = (res.radius + dist) * 0.5;
+                        res.center = res.center + (point - res.center) * ((radius - res.radius) / dist);
+                        res.radius = radius;
+                    }
+                }
+                return Some(res);
+            }
+
+            /// Minimal enclosing sphere of the points (Welzl). `None` for an empty slice.
+            pub fn welzl(points: &[$vec3]) -> Option<Self> {
+                let mut res = Self::new(*points.first()?, 0.0);
+                for i in 1..points.len() {
+                    if !res.contains_approx(points[i]) {
+                        res = Self::welzl1(&points[..i], points[i]);
+                    }
+                }
+                return Some(res);
+            }
+
+            fn welzl1(points: &[$vec3], a: $vec3) -> Self {
+                let mut res = Self::new(a, 0.0);
+                for i in 0..points.len() {
+                    if !res.contains_approx(points[i]) {
+                        res = Self::welzl2(&points[..i], a, points[i]);
+                    }
+                }
+                return res;
+            }
+
+            fn welzl2(points: &[$vec3], a: $vec3, b: $vec3) -> Self {
+                let mut res = Self::from2(a, b);
+                for i in 0..points.len() {
+                    if !res.contains_approx(points[i]) {
+                        res = Self::welzl3(&points[..i], a, b, points[i]);
+                    }
+                }
+                return res;
+            }
+
+            fn welzl3(points: &[$vec3], a: $vec3, b: $vec3, c: $vec3) -> Self {
+                let mut res = Self::from3(a, b, c);
+                for &point in points {
+                    if !res.contains_approx(point) {
+                        res = Self::from4(a, b, c, point);
+                    }
+                }
+                return res;
+            }
+
+            fn from2(a: $vec3, b: $vec3) -> Self {
+                return Self { center: (a + b) * 0.5, radius: a.dist_to(b) * 0.5 };
+            }
+
+            // circumscribed circle, or the sphere around the farthest pair for collinear points
+            fn from3(a: $vec3, b: $vec3, c: $vec3) -> Self {
+                let ab = b - a;
+                let ac = c - a;
+                let normal = ab.cross(ac);
+                let denom = 2.0 * normal.sqr_magnitude();
+                if denom <= <$typ>::EPSILON * ab.sqr_magnitude() * ac.sqr_magnitude() {
+                    return [Self::from2(a, b), Self::from2(a, c), Self::from2(b, c)]
+                        .into_iter()
+                        .fold(Self::new(a, 0.0), |best, s| if s.radius > best.radius { s } else { best });
+                }
+
+                let offset = (normal.cross(ab) * ac.sqr_magnitude() + ac.cross(normal) * ab.sqr_magnitude()) / denom;
+                return Self { center: a + offset, radius: offset.magnitude() };
+            }
+
+            // circumscribed sphere, or the smallest sphere of three of the points containing the fourth for
+            // coplanar points
+            fn from4(a: $vec3, b: $vec3, c: $vec3, d: $vec3) -> Self {
+                let u = b - a;
+                let v = c - a;
+                let w = d - a;
+                let denom = 2.0 * u.dot(v.cross(w));
+                if denom.abs() <= <$typ>::EPSILON * u.magnitude() * v.magnitude() * w.magnitude() {
+                    return [Self::from3(a, b, c), Self::from3(a, b, d), Self::from3(a, c, d), Self::from3(b, c, d)]
+                        .into_iter()
+                        .filter(|s| s.contains_approx(a) && s.contains_approx(b) && s.contains_approx(c) && s.contains_approx(d))
+                        .fold(Self::from3(a, b, c).merge(&Self::new(d, 0.0)), |best, s| if s.radius < best.radius { s } else { best });
+                }
+
+                let offset = (v.cross(w) * u.sqr_magnitude() + w.cross(u) * v.sqr_magnitude() + u.cross(v) * w.sqr_magnitude()) / denom;
+                return Self { center: a + offset, radius: offset.magnitude() };
+            }
+
+            // leaves room for the rounding of the constructions above, so points on the boundary count
+            fn contains_approx(&self, point: $vec3) -> bool {
+                let tolerance = self.radius.max(1.0) * <$typ>::EPSILON.sqrt();
+                return point.dist_to(self.center) <= self.radius + tolerance;
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! gen_circle {
+    ($ident:ident, $vec2:ident, $mat3:ident, $typ:ty) => {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $ident {
+            pub center: $vec2,
+            pub radius: $typ,
+        }
+
+        impl $ident {
+            pub const fn new(center: $vec2, radius: $typ) -> Self {
+                return Self { center, radius };
+            }
+
+            pub fn contains(&self, point: $vec2) -> bool {
+                return (point - self.center).sqr_magnitude() <= self.radius * self.radius;
+            }
+
+            pub fn contains_circle(&self, other: &Self) -> bool {
+                return self.center.dist_to(other.center) + other.radius <= self.radius;
+            }
+
+            pub fn intersects(&self, other: &Self) -> bool {
+                let radius = self.radius + other.radius;
+                return (other.center - self.center).sqr_magnitude() <= radius * radius;
+            }
+
+            /// Smallest circle containing both circles.
+            pub fn merge(&self, other: &Self) -> Self {
+                let dist = self.center.dist_to(other.center);
+                if dist + other.radius <= self.radius {
+                    return *self;
+                }
+                if dist + self.radius <= other.radius {
+                    return *other;
+                }
+
+                let radius = (dist + self.radius + other.radius) * 0.5;
+                let center = self.center + (other.center - self.center) * ((radius - self.radius) / dist);
+                return Self { center, radius };
+            }
+
+            /// Transforms the circle by an affine matrix. With a non-uniform scale the result is the circle
+            /// around the resulting ellipse, scaled by the largest axis.
+            pub fn transform(&self, mat: &$mat3) -> Self {
+                let center = $vec2(
+                    mat[(0, 0)] * self.center.0 + mat[(0, 1)] * self.center.1 + mat[(0, 2)],
+                    mat[(1, 0)] * self.center.0 + mat[(1, 1)] * self.center.1 + mat[(1, 2)],
+                );
+                let scale = (0..2)
+                    .map(|col| $vec2(mat[(0, col)], mat[(1, col)]).magnitude())
+                    .fold(0.0, <$typ>::max);
+
+                return Self { center, radius: self.radius * scale };
+            }
+
+            /// Fast approximation of the enclosing circle (Ritter), usually a few percent bigger than the
+            /// minimal one. `None` for an empty slice.
+            pub fn ritter(points: &[$vec2]) -> Option<Self> {
+                let first = *points.first()?;
+                let farthest = |from: $vec2| -> $vec2 {
+                    return points.iter().copied().fold(from, |best, p| {
+                        if p.sqr_dist_to(from) > best.sqr_dist_to(from) { p } else { best }
+                    });
+                };
+
+                let a = farthest(first);
+                let b = farthest(a);
+                let mut res = Self::from2(a, b);
+                for &point in points {
+                    let dist = point.dist_to(res.center);
+                    if dist > res.radius {
+                        let radius = (res.radius + dist) * 0.5;
+                        res.center = res.center + (point - res.center) * ((radius - res.radius) / dist);
+                        res.radius = radius;
+                    }
+                }
+                return Some(res);
+            }
+
+            /// Minimal enclosing circle of the points (Welzl). `None` for an empty slice.
+            pub fn welzl(points: &[$vec2]) -> Option<Self> {
+                let mut res = Self::new(*points.first()?, 0.0);
+                for i in 1..points.len() {
+                    if !res.contains_approx(points[i]) {
+                        res = Self::welzl1(&points[..i], points[i]);
+                    }
+                }
+                return Some(res);
+            }
+
+            fn welzl1(points: &[$vec2], a: $vec2) -> Self {
+                let mut res = Self::new(a, 0.0);
+                for i in 0..points.len() {
+                    if !res.contains_approx(points[i]) {
+                        res = Self::welzl2(&points[..i], a, points[i]);
+                    }
+                }
+                return res;
+            }
+
+            fn welzl2(points: &[$vec2], a: $vec2, b: $vec2) -> Self {
+                let mut res = Self::from2(a, b);
+                for &point in points {
+                    if !res.contains_approx(point) {
+                        res = Self::from3(a, b, point);
+                    }
+                }
+                return res;
+            }
+
+            fn from2(a: $vec2, b: $vec2) -> Self {
+                return Self { center: (a + b) * 0.5, radius: a.dist_to(b) * 0.5 };
+            }
+
+            // circumscribed circle, or the circle around the farthest pair for collinear points
+            fn from3(a: $vec2, b: $vec2, c: $vec2) -> Self {
+                let ab = b - a;
+                let ac = c - a;
+                let denom = 2.0 * (ab.0 * ac.1 - ab.1 * ac.0);
+                if denom.abs() <= <$typ>::EPSILON * ab.magnitude() * ac.magnitude() {
+                    return [Self::from2(a, b), Self::from2(a, c), Self::from2(b, c)]
+                        .into_iter()
+                        .fold(Self::new(a, 0.0), |best, s| if s.radius > best.radius { s } else { best });
+                }
+
+                let ab_sqr = ab.sqr_magnitude();
+                let ac_sqr = ac.sqr_magnitude();
+                let offset = $vec2(ac.1 * ab_sqr - ab.1 * ac_sqr, ab.0 * ac_sqr - ac.0 * ab_sqr) / denom;
+                return Self { center: a + offset, radius: offset.magnitude() };
+            }
+
+            // leaves room for the rounding of the constructions above, so points on the boundary count
+            fn contains_approx(&self, point: $vec2) -> bool {
+                let tolerance = self.radius.max(1.0) * <$typ>::EPSILON.sqrt();
+                return point.dist_to(self.center) <= self.radius + tolerance;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // small LCG so the tests don't need a rand dependency
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> f64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            return (self.0 >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0;
+        }
+
+        fn vec3(&mut self) -> Vec3d {
+            return Vec3d(self.next(), self.next() * 2.0, self.next() * 0.5);
+        }
+
+        fn vec2(&mut self) -> Vec2d {
+            return Vec2d(self.next(), self.next() * 2.0);
+        }
+    }
+
+    fn sphere_contains_all(sphere: &Sphered, points: &[Vec3d]) -> bool {
+        return points.iter().all(|p| p.dist_to(sphere.center) <= sphere.radius + 1e-9);
+    }
+
+    fn circle_contains_all(circle: &Circled, points: &[Vec2d]) -> bool {
+        return points.iter().all(|p| p.dist_to(circle.center) <= circle.radius + 1e-9);
+    }
+
+    // smallest sphere through 2, 3 or 4 of the points that contains all of them
+    fn brute_force_sphere(points: &[Vec3d]) -> Sphered {
+        let n = points.len();
+        let mut candidates = Vec::new();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (a, b) = (points[i], points[j]);
+                candidates.push(Sphered::new((a + b) * 0.5, a.dist_to(b) * 0.5));
+
+                for k in j + 1..n {
+                    let c = points[k];
+                    let (ab, ac) = (b - a, c - a);
+                    let normal = ab.cross(ac);
+                    let offset = (normal.cross(ab) * ac.sqr_magnitude() + ac.cross(normal) * ab.sqr_magnitude())
+                        / (2.0 * normal.sqr_magnitude());
+                    candidates.push(Sphered::new(a + offset, offset.magnitude()));
+
+                    for &d in &points[k + 1..] {
+                        let rows = [b - a, c - a, d - a];
+                        let mat = Mat3d::new(rows.map(|r| [r.0 * 2.0, r.1 * 2.0, r.2 * 2.0]));
+                        let rhs = Vec3d(b.sqr_magnitude(), c.sqr_magnitude(), d.sqr_magnitude()) - Vec3d::one(a.sqr_magnitude());
+                        if let Some(inv) = mat.inverse() {
+                            let center = &inv * rhs;
+                            candidates.push(Sphered::new(center, center.dist_to(a)));
+                        }
+                    }
+                }
+            }
+        }
+
+        return candidates.into_iter()
+            .filter(|sphere| sphere_contains_all(sphere, points))
+            .min_by(|a, b| a.radius.total_cmp(&b.radius))
+            .unwrap();
+    }
+
+    fn brute_force_circle(points: &[Vec2d]) -> Circled {
+        let n = points.len();
+        let mut candidates = Vec::new();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (a, b) = (points[i], points[j]);
+                candidates.push(Circled::new((a + b) * 0.5, a.dist_to(b) * 0.5));
+
+                for &c in &points[j + 1..] {
+                    let (ab, ac) = (b - a, c - a);
+                    let det = 2.0 * (ab.0 * ac.1 - ab.1 * ac.0);
+                    if det == 0.0 {
+                        continue;
+                    }
+                    let (ab_sqr, ac_sqr) = (ab.sqr_magnitude(), ac.sqr_magnitude());
+                    let offset = Vec2d(ac.1 * ab_sqr - ab.1 * ac_sqr, ab.0 * ac_sqr - ac.0 * ab_sqr) / det;
+                    candidates.push(Circled::new(a + offset, offset.magnitude()));
+                }
+            }
+        }
+
+        return candidates.into_iter()
+            .filter(|circle| circle_contains_all(circle, points))
+            .min_by(|a, b| a.radius.total_cmp(&b.radius))
+            .unwrap();
+    }
+
+    #[test]
+    fn welzl_sphere_matches_brute_force() {
+        let mut rng = Rng(7);
+        for count in [2, 3, 4, 5, 8, 12] {
+            for _ in 0..20 {
+                let points = (0..count).map(|_| rng.vec3()).collect::<Vec<_>>();
+                let sphere = Sphered::welzl(&points).unwrap();
+                let expected = brute_force_sphere(&points);
+                assert!(sphere_contains_all(&sphere, &points), "{:?}", sphere);
+                assert!((sphere.radius - expected.radius).abs() < 1e-9, "{:?} != {:?}", sphere, expected);
+                assert!(sphere.center.dist_to(expected.center) < 1e-6, "{:?} != {:?}", sphere, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn welzl_circle_matches_brute_force() {
+        let mut rng = Rng(11);
+        for count in [2, 3, 4, 5, 8, 12] {
+            for _ in 0..20 {
+                let points = (0..count).map(|_| rng.vec2()).collect::<Vec<_>>();
+                let circle = Circled::welzl(&points).unwrap();
+                let expected = brute_force_circle(&points);
+                assert!(circle_contains_all(&circle, &points), "{:?}", circle);
+                assert!((circle.radius - expected.radius).abs() < 1e-9, "{:?} != {:?}", circle, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn welzl_degenerate_inputs() {
+        assert_eq!(Sphered::welzl(&[]), None);
+        assert_eq!(Circled::welzl(&[]), None);
+        assert_eq!(Sphered::welzl(&[Vec3d::ONE]), Some(Sphered::new(Vec3d::ONE, 0.0)));
+
+        let repeated = [Vec3d::ONE, Vec3d::ONE, Vec3d(3.0, 1.0, 1.0), Vec3d(3.0, 1.0, 1.0)];
+        let sphere = Sphered::welzl(&repeated).unwrap();
+        assert!((sphere.radius - 1.0).abs() < 1e-9);
+
+        // collinear and coplanar points
+        let line = [Vec3d::ZERO, Vec3d(1.0, 1.0, 1.0), Vec3d(2.0, 2.0, 2.0), Vec3d(0.5, 0.5, 0.5)];
+        let sphere = Sphered::welzl(&line).unwrap();
+        assert!((sphere.radius - 3.0_f64.sqrt()).abs() < 1e-9);
+        let square = [Vec3d(1.0, 0.0, 1.0), Vec3d(-1.0, 0.0, 1.0), Vec3d(1.0, 0.0, -1.0), Vec3d(-1.0, 0.0, -1.0), Vec3d::ZERO];
+        let sphere = Sphered::welzl(&square).unwrap();
+        assert!((sphere.radius - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ritter_contains_every_point() {
+        let mut rng = Rng(3);
+        for _ in 0..20 {
+            let points = (0..30).map(|_| rng.vec3()).collect::<Vec<_>>();
+            let sphere = Sphered::ritter(&points).unwrap();
+            assert!(sphere_contains_all(&sphere, &points));
+            assert!(sphere.radius >= Sphered::welzl(&points).unwrap().radius - 1e-9);
+
+            let points = (0..30).map(|_| rng.vec2()).collect::<Vec<_>>();
+            let circle = Circled::ritter(&points).unwrap();
+            assert!(circle_contains_all(&circle, &points));
+        }
+        assert_eq!(Sphered::ritter(&[]), None);
+    }
+
+    #[test]
+    fn merge_is_the_smallest_enclosing_sphere() {
+        let a = Sphered::new(Vec3d::ZERO, 1.0);
+        let b = Sphered::new(Vec3d(4.0, 0.0, 0.0), 2.0);
+        let merged = a.merge(&b);
+        assert_eq!(merged, Sphered::new(Vec3d(2.5, 0.0, 0.0), 3.5));
+        assert!(merged.contains_sphere(&a) && merged.contains_sphere(&b));
+
+        let inner = Sphered::new(Vec3d(0.5, 0.0, 0.0), 0.25);
+        assert_eq!(a.merge(&inner), a);
+        assert_eq!(inner.merge(&a), a);
+
+        assert!(a.intersects(&Sphered::new(Vec3d(3.0, 0.0, 0.0), 2.0)));
+        assert!(!a.intersects(&Sphered::new(Vec3d(3.5, 0.0, 0.0), 2.0)));
+
+        let merged = Circled::new(Vec2d::ZERO, 1.0).merge(&Circled::new(Vec2d(0.0, 4.0), 1.0));
+        assert_eq!(merged, Circled::new(Vec2d(0.0, 2.0), 3.0));
+    }
+
+    #[test]
+    fn transform_uses_the_largest_scale() {
+        let sphere = Sphered::new(Vec3d(1.0, 0.0, 0.0), 2.0);
+        let mat = Mat4d::tf_matrix(Vec3d(0.0, 5.0, 0.0), Quatd::rotator(Vec3d(0.0, 0.0, std::f64::consts::FRAC_PI_2)), Vec3d(1.0, 3.0, 0.5));
+        let res = sphere.transform(&mat);
+        assert!((res.center - Vec3d(0.0, 6.0, 0.0)).magnitude() < 1e-9);
+        assert!((res.radius - 6.0).abs() < 1e-9);
+
+        let circle = Circled::new(Vec2d(1.0, 0.0), 2.0).transform(&Mat3d::tf_matrix(Vec2d(0.0, 5.0), 0.0, Vec2d(2.0, 0.5)));
+        assert_eq!(circle, Circled::new(Vec2d(2.0, 5.0), 4.0));
+    }
+}
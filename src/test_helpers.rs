@@ -0,0 +1,28 @@
+// Assertions and conversions shared by the unit tests, which all work on the `f64` types.
+
+use crate::traits::{InnerSpace, Matrix, Scalar, Vector};
+use crate::types::{Mat3d, Mat4d, Vec2d, Vec3d, Vec4d};
+
+pub const EPSILON: f64 = 1e-9;
+
+pub fn assert_vec_eq<V: InnerSpace>(a: V, b: V) {
+    assert!((a - b).magnitude().to_f64() < EPSILON, "{:?} != {:?}", a, b);
+}
+
+pub fn assert_mat_eq<M: Matrix>(a: &M, b: &M) {
+    for i in 0..M::DIM {
+        assert!((a.row(i) - b.row(i)).sqr_magnitude().to_f64().sqrt() < EPSILON, "{:?} != {:?}", a, b);
+    }
+}
+
+/// Transforms a point by a 3D affine or projective matrix, dividing by w.
+pub fn mat4_point(mat: &Mat4d, point: Vec3d) -> Vec3d {
+    let res = mat * Vec4d(point.0, point.1, point.2, 1.0);
+    return Vec3d(res.0, res.1, res.2) / res.3;
+}
+
+/// Transforms a point by a 2D affine matrix.
+pub fn mat3_point(mat: &Mat3d, point: Vec2d) -> Vec2d {
+    let res = mat * Vec3d(point.0, point.1, 1.0);
+    return Vec2d(res.0, res.1) / res.2;
+}
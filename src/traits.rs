@@ -0,0 +1,288 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Numeric type the vectors and matrices are built from.
+pub trait Scalar:
+    Copy + PartialEq + PartialOrd + Debug + Display +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> +
+    AddAssign + SubAssign + MulAssign + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Converts from a `f64`, truncating like an `as` cast.
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+}
+
+/// Floating point scalar.
+pub trait Float: Scalar + Neg<Output = Self> {
+    const PI: Self;
+    const EPSILON: Self;
+
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+}
+
+/// A fixed size vector, implemented by every type generated by `scalar_vecN_impl`.
+pub trait Vector:
+    Copy + PartialEq + Debug +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Self::Scalar, Output = Self> + Div<Self::Scalar, Output = Self> +
+    AddAssign + SubAssign + MulAssign<Self::Scalar> + DivAssign<Self::Scalar>
+{
+    type Scalar: Scalar;
+
+    /// Number of components.
+    const DIM: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Creates a vector with every component set to `value`.
+    fn splat(value: Self::Scalar) -> Self;
+
+    /// Performs the dot product between two vectors.
+    fn dot(self, other: Self) -> Self::Scalar;
+    fn sqr_magnitude(self) -> Self::Scalar;
+
+    /// Multiplies the vectors component-wise
+    fn scale(self, other: Self) -> Self;
+
+    /// Divides the vectors component-wise
+    fn inv_scale(self, other: Self) -> Self;
+
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn clamp(self, min: Self, max: Self) -> Self;
+    fn max_axis(self) -> Self::Scalar;
+    fn min_axis(self) -> Self::Scalar;
+}
+
+/// A vector over a floating point scalar, implemented by every type generated by `float_vecN_impl`.
+pub trait InnerSpace: Vector<Scalar: Float> + Neg<Output = Self> {
+    /// Returns the magnitude of the vector.
+    fn magnitude(self) -> Self::Scalar;
+
+    /// Returns the vector with a magnitude of 1.
+    fn normalized(self) -> Self;
+
+    fn dist_to(self, other: Self) -> Self::Scalar;
+    fn sqr_dist_to(self, other: Self) -> Self::Scalar;
+
+    /// Linearly interpolates between `self` and `other`.
+    fn lerp(self, other: Self, t: Self::Scalar) -> Self {
+        self + (other - self) * t
+    }
+}
+
+/// A square matrix, implemented by every type generated by `gen_matNxN`.
+pub trait Matrix: Clone + PartialEq + Debug {
+    type Scalar: Float;
+    type Vector: Vector<Scalar = Self::Scalar>;
+
+    /// Number of rows and columns.
+    const DIM: usize;
+    const IDENTITY: Self;
+
+    fn row(&self, row: usize) -> Self::Vector;
+    fn col(&self, col: usize) -> Self::Vector;
+    fn determinant(&self) -> Self::Scalar;
+    fn transp(&self) -> Self;
+    fn inverse(&self) -> Option<Self>;
+
+    /// Computes `self * other`.
+    fn mul_mat(&self, other: &Self) -> Self;
+
+    /// Computes `self * vec`.
+    fn mul_vec(&self, vec: Self::Vector) -> Self::Vector;
+}
+
+/// A rotation acting on vectors, implemented by the quaternions wired up with `rot_vec3_impl`.
+pub trait Rotation: Copy + Mul<Output = Self> {
+    type Vector: InnerSpace;
+
+    const IDENTITY: Self;
+
+    fn rotate_vector(self, vec: Self::Vector) -> Self::Vector;
+
+    /// Returns the rotation that undoes `self`.
+    fn invert(self) -> Self;
+}
+
+/// Returns the mean of all the vectors in the slice, or `None` if the slice is empty.
+/// Integer vectors round like the integer division.
+pub fn average<V: Vector>(slice: &[V]) -> Option<V> {
+    if slice.is_empty() {
+        return None;
+    }
+
+    let mut res = V::ZERO;
+    for v in slice {
+        res += *v;
+    }
+    return Some(res / V::Scalar::from_f64(slice.len() as f64));
+}
+
+/// Returns the component-wise minimum and maximum of all the vectors in the slice,
+/// or `None` if the slice is empty.
+pub fn bounding_box<V: Vector>(slice: &[V]) -> Option<(V, V)> {
+    let first = *slice.first()?;
+    let mut min = first;
+    let mut max = first;
+    for v in &slice[1..] {
+        min = min.min(*v);
+        max = max.max(*v);
+    }
+    return Some((min, max));
+}
+
+/// Linearly interpolates between `a` and `b`.
+pub fn lerp<V: InnerSpace>(a: V, b: V, t: V::Scalar) -> V {
+    return a.lerp(b, t);
+}
+
+macro_rules! scalar_impl {
+    ($typ:ty, $zero:literal, $one:literal) => {
+        impl Scalar for $typ {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+
+            fn from_f64(value: f64) -> Self {
+                value as $typ
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    };
+}
+
+macro_rules! float_impl {
+    ($typ:ident) => {
+        impl Float for $typ {
+            const PI: Self = std::$typ::consts::PI;
+            const EPSILON: Self = $typ::EPSILON;
+
+            fn sqrt(self) -> Self { $typ::sqrt(self) }
+            fn abs(self) -> Self { $typ::abs(self) }
+            fn sin(self) -> Self { $typ::sin(self) }
+            fn cos(self) -> Self { $typ::cos(self) }
+            fn tan(self) -> Self { $typ::tan(self) }
+            fn asin(self) -> Self { $typ::asin(self) }
+            fn acos(self) -> Self { $typ::acos(self) }
+            fn atan2(self, other: Self) -> Self { $typ::atan2(self, other) }
+            fn floor(self) -> Self { $typ::floor(self) }
+            fn ceil(self) -> Self { $typ::ceil(self) }
+            fn round(self) -> Self { $typ::round(self) }
+        }
+    };
+}
+
+scalar_impl!(f32, 0.0, 1.0);
+scalar_impl!(f64, 0.0, 1.0);
+scalar_impl!(i32, 0, 1);
+scalar_impl!(u32, 0, 1);
+
+float_impl!(f32);
+float_impl!(f64);
+
+/// Implements [`Vector`] by forwarding to the inherent methods generated by `scalar_vecN_impl`.
+#[macro_export]
+macro_rules! vector_trait_impl {
+    ($ident:ident, $typ:ty, $dim:literal) => {
+        impl $crate::traits::Vector for $ident {
+            type Scalar = $typ;
+
+            const DIM: usize = $dim;
+            const ZERO: Self = Self::ZERO;
+            const ONE: Self = Self::ONE;
+
+            fn splat(value: $typ) -> Self { Self::one(value) }
+            fn dot(self, other: Self) -> $typ { Self::dot(self, other) }
+            fn sqr_magnitude(self) -> $typ { Self::sqr_magnitude(self) }
+            fn scale(self, other: Self) -> Self { Self::scale(self, other) }
+            fn inv_scale(self, other: Self) -> Self { Self::inv_scale(self, other) }
+            fn min(self, other: Self) -> Self { Self::min(self, other) }
+            fn max(self, other: Self) -> Self { Self::max(self, other) }
+            fn clamp(self, min: Self, max: Self) -> Self { Self::clamp(self, min, max) }
+            fn max_axis(self) -> $typ { Self::max_axis(self) }
+            fn min_axis(self) -> $typ { Self::min_axis(self) }
+        }
+    };
+}
+
+/// Implements [`InnerSpace`] by forwarding to the inherent methods generated by `float_vecN_impl`.
+#[macro_export]
+macro_rules! inner_space_trait_impl {
+    ($ident:ident, $typ:ty) => {
+        impl $crate::traits::InnerSpace for $ident {
+            fn magnitude(self) -> $typ { Self::magnitude(self) }
+            fn normalized(self) -> Self { Self::normalized(self) }
+            fn dist_to(self, other: Self) -> $typ { Self::dist_to(self, other) }
+            fn sqr_dist_to(self, other: Self) -> $typ { Self::sqr_dist_to(self, other) }
+        }
+    };
+}
+
+/// Implements [`Matrix`] by forwarding to the inherent methods generated by `gen_matNxN`.
+#[macro_export]
+macro_rules! matrix_trait_impl {
+    ($ident:ident, $vec:ident, $typ:ty, $dim:literal) => {
+        impl $crate::traits::Matrix for $ident {
+            type Scalar = $typ;
+            type Vector = $vec;
+
+            const DIM: usize = $dim;
+            const IDENTITY: Self = Self::IDENTITY;
+
+            fn row(&self, row: usize) -> $vec { Self::row(self, row) }
+            fn col(&self, col: usize) -> $vec { Self::col(self, col) }
+            fn determinant(&self) -> $typ { Self::determinant(self) }
+            fn transp(&self) -> Self { Self::transp(self) }
+            fn inverse(&self) -> Option<Self> { Self::inverse(self) }
+            fn mul_mat(&self, other: &Self) -> Self { self * other }
+            fn mul_vec(&self, vec: $vec) -> $vec { self * vec }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    #[test]
+    fn average_of_empty_slice_is_none() {
+        assert_eq!(average::<Vec2u>(&[]), None);
+        assert_eq!(average::<Vec3f>(&[]), None);
+        assert_eq!(average(&[Vec2i(1, 2), Vec2i(3, 6)]), Some(Vec2i(2, 4)));
+        assert_eq!(average(&[Vec3f(1.0, 0.0, 2.0), Vec3f(0.0, 1.0, 4.0)]), Some(Vec3f(0.5, 0.5, 3.0)));
+    }
+
+    #[test]
+    fn bounding_box_of_points() {
+        assert_eq!(bounding_box::<Vec2u>(&[]), None);
+        assert_eq!(
+            bounding_box(&[Vec2i(3, -1), Vec2i(-2, 5), Vec2i(0, 0)]),
+            Some((Vec2i(-2, -1), Vec2i(3, 5))),
+        );
+    }
+
+    #[test]
+    fn lerp_endpoints_and_middle() {
+        let (a, b) = (Vec3f(1.0, 2.0, 3.0), Vec3f(-3.0, 4.0, 7.0));
+        assert_eq!(lerp(a, b, 0.0), a);
+        assert_eq!(lerp(a, b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Vec3f(-1.0, 3.0, 5.0));
+    }
+}
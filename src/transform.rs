@@ -0,0 +1,347 @@
+#[macro_export]
+macro_rules! gen_transform3d {
+    ($ident:ident, $vec3:ident, $quat:ident, $mat4:ident, $typ:ty) => {
+        /// Translation, rotation and scale, applied as scale first, then rotation, then translation,
+        /// matching `tf_matrix`.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $ident {
+            pub translation: $vec3,
+            pub rotation: $quat,
+            pub scale: $vec3,
+        }
+
+        impl $ident {
+            pub const IDENT: Self = Self { translation: $vec3::ZERO, rotation: $quat::IDENT, scale: $vec3::ONE };
+
+            pub const fn new(translation: $vec3, rotation: $quat, scale: $vec3) -> Self {
+                return Self { translation, rotation, scale };
+            }
+
+            pub fn transform_point(&self, point: $vec3) -> $vec3 {
+                return point.scale(self.scale).rotate(self.rotation) + self.translation;
+            }
+
+            /// Like `transform_point` but ignores the translation, for directions and offsets.
+            pub fn transform_vector(&self, vec: $vec3) -> $vec3 {
+                return vec.scale(self.scale).rotate(self.rotation);
+            }
+
+            /// Exact inverse of `transform_point`, even with non-uniform scale.
+            pub fn inverse_transform_point(&self, point: $vec3) -> $vec3 {
+                return (point - self.translation).rotate(self.rotation.conjugate()).inv_scale(self.scale);
+            }
+
+            /// Returns the transform that undoes `self`. Exact for uniform scales, with non-uniform scale
+            /// and a rotation the real inverse has shear and can't be stored, use `inverse_transform_point` then.
+            pub fn inverse(&self) -> Self {
+                let rotation = self.rotation.conjugate();
+                let scale = $vec3::ONE.inv_scale(self.scale);
+                let translation = (-self.translation).rotate(rotation).scale(scale);
+
+                return Self { translation, rotation, scale };
+            }
+
+            /// Interpolates translation and scale linearly and rotation spherically.
+            pub fn lerp(&self, other: &Self, t: $typ) -> Self {
+                return Self {
+                    translation: self.translation + (other.translation - self.translation) * t,
+                    rotation: self.rotation.slerp(other.rotation, t),
+                    scale: self.scale + (other.scale - self.scale) * t,
+                };
+            }
+
+            pub fn to_mat4(&self) -> $mat4 {
+                return $mat4::tf_matrix(self.translation, self.rotation, self.scale);
+            }
+
+            /// Recovers the transform from a matrix built by `to_mat4` or `tf_matrix`, see `Mat4::decompose`.
+            /// Returns `None` if the matrix has shear, a projective part or a zero scale.
+            pub fn from_mat4(mat: &$mat4) -> Option<Self> {
+                let (translation, rotation, scale) = mat.decompose()?;
+                return Some(Self { translation, rotation, scale });
+            }
+        }
+
+        impl std::ops::Mul for $ident {
+            type Output = Self;
+
+            /// Applies `rhs` first, then `self`. Exact unless `self` has a non-uniform scale and `rhs` a rotation.
+            fn mul(self, rhs: Self) -> Self::Output {
+                return Self {
+                    translation: self.transform_point(rhs.translation),
+                    rotation: self.rotation * rhs.rotation,
+                    scale: self.scale.scale(rhs.scale),
+                };
+            }
+        }
+
+        impl std::ops::MulAssign for $ident {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl $crate::hierarchy::HierarchyTransform for $ident {
+            type Matrix = $mat4;
+
+            fn to_matrix(&self) -> $mat4 {
+                return self.to_mat4();
+            }
+
+            fn from_matrix(mat: &$mat4) -> Option<Self> {
+                return Self::from_mat4(mat);
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! gen_transform2d {
+    ($ident:ident, $vec2:ident, $mat3:ident, $typ:ty) => {
+        /// 2D position, counter-clockwise rotation in radians and scale, applied as scale first, then rotation,
+        /// then translation, matching `tf_matrix`.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $ident {
+            pub pos: $vec2,
+            pub rot: $typ,
+            pub scale: $vec2,
+        }
+
+        impl $ident {
+            pub const IDENT: Self = Self { pos: $vec2::ZERO, rot: 0.0, scale: $vec2::ONE };
+
+            pub const fn new(pos: $vec2, rot: $typ, scale: $vec2) -> Self {
+                return Self { pos, rot, scale };
+            }
+
+            pub fn transform_point(&self, point: $vec2) -> $vec2 {
+                return point.scale(self.scale).rotate(self.rot) + self.pos;
+            }
+
+            /// Like `transform_point` but ignores the translation, for directions and offsets.
+            pub fn transform_vector(&self, vec: $vec2) -> $vec2 {
+                return vec.scale(self.scale).rotate(self.rot);
+            }
+
+            /// Exact inverse of `transform_point`, even with non-uniform scale.
+            pub fn inverse_transform_point(&self, point: $vec2) -> $vec2 {
+                return (point - self.pos).rotate(-self.rot).inv_scale(self.scale);
+            }
+
+            /// Returns the transform that undoes `self`. Exact for uniform scales, with non-uniform scale
+            /// and a rotation the real inverse has shear and can't be stored, use `inverse_transform_point` then.
+            pub fn inverse(&self) -> Self {
+                let scale = $vec2::ONE.inv_scale(self.scale);
+                let pos = (-self.pos).rotate(-self.rot).scale(scale);
+
+                return Self { pos, rot: -self.rot, scale };
+            }
+
+            /// Interpolates position and scale linearly and rotation along the shortest arc.
+            pub fn lerp(&self, other: &Self, t: $typ) -> Self {
+                let pi = <$typ as $crate::traits::Float>::PI;
+                let diff = (other.rot - self.rot + pi).rem_euclid(2.0 * pi) - pi;
+
+                return Self {
+                    pos: self.pos + (other.pos - self.pos) * t,
+                    rot: self.rot + diff * t,
+                    scale: self.scale + (other.scale - self.scale) * t,
+                };
+            }
+
+            pub fn to_mat3(&self) -> $mat3 {
+                return $mat3::tf_matrix(self.pos, self.rot, self.scale);
+            }
+
+            /// Recovers the transform from a matrix built by `to_mat3` or `tf_matrix`, see `Mat3::decompose`.
+            /// Returns `None` if the matrix has shear, a projective part or a zero scale.
+            pub fn from_mat3(mat: &$mat3) -> Option<Self> {
+                let (pos, rot, scale) = mat.decompose()?;
+                return Some(Self { pos, rot, scale });
+            }
+
+            /// Returns the top two rows of `to_mat3` in column-major order (the columns are the transformed
+            /// x axis, y axis and the position), matching a `mat3x2` in GLSL or `float3x2` in HLSL.
+            pub fn to_affine(&self) -> [[$typ; 2]; 3] {
+                let right = self.transform_vector($vec2::RIGHT);
+                let up = self.transform_vector($vec2::UP);
+
+                return [
+                    [right.0, right.1],
+                    [up.0, up.1],
+                    [self.pos.0, self.pos.1],
+                ];
+            }
+        }
+
+        impl std::ops::Mul for $ident {
+            type Output = Self;
+
+            /// Applies `rhs` first, then `self`. Exact unless `self` has a non-uniform scale and `rhs` a rotation.
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn mul(self, rhs: Self) -> Self::Output {
+                return Self {
+                    pos: self.transform_point(rhs.pos),
+                    rot: self.rot + rhs.rot,
+                    scale: self.scale.scale(rhs.scale),
+                };
+            }
+        }
+
+        impl std::ops::MulAssign for $ident {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl $crate::hierarchy::HierarchyTransform for $ident {
+            type Matrix = $mat3;
+
+            fn to_matrix(&self) -> $mat3 {
+                return self.to_mat3();
+            }
+
+            fn from_matrix(mat: &$mat3) -> Option<Self> {
+                return Self::from_mat3(mat);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::{assert_vec_eq, mat3_point, mat4_point};
+
+    fn transforms3d() -> Vec<Transform3Dd> {
+        return vec![
+            Transform3Dd::IDENT,
+            Transform3Dd::new(Vec3d(1.0, -2.0, 3.0), Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d::ONE),
+            Transform3Dd::new(Vec3d(0.5, 0.0, -4.0), Quatd::rotator(Vec3d(0.0, 2.0, 0.0)), Vec3d(2.0, 0.5, 3.0)),
+            Transform3Dd::new(Vec3d(0.0, 1.0, 0.0), Quatd::rotator(Vec3d(-2.0, 0.5, 0.1)), Vec3d(-1.5, 2.0, 0.25)),
+        ];
+    }
+
+    #[test]
+    fn transform3d_matches_to_mat4() {
+        for tf in transforms3d() {
+            let mat = tf.to_mat4();
+            let point = Vec3d(0.5, -2.0, 3.0);
+            assert_vec_eq(tf.transform_point(point), mat4_point(&mat, point));
+            assert_vec_eq(tf.transform_vector(point), mat4_point(&mat, point) - mat4_point(&mat, Vec3d::ZERO));
+            assert_vec_eq(tf.inverse_transform_point(tf.transform_point(point)), point);
+        }
+    }
+
+    #[test]
+    fn transform3d_mat4_round_trip() {
+        for tf in transforms3d() {
+            let back = Transform3Dd::from_mat4(&tf.to_mat4()).unwrap();
+            for point in [Vec3d::ZERO, Vec3d::RIGHT, Vec3d::UP, Vec3d(0.5, -2.0, 3.0)] {
+                assert_vec_eq(back.transform_point(point), tf.transform_point(point));
+            }
+        }
+    }
+
+    #[test]
+    fn transform3d_from_mat4_rejects_shear() {
+        let mut mat = Mat4d::tf_matrix(Vec3d(1.0, 2.0, 3.0), Quatd::IDENT, Vec3d::ONE);
+        mat[(0, 1)] = 0.5;
+        assert_eq!(Transform3Dd::from_mat4(&mat), None);
+        assert_eq!(mat.decompose(), None);
+
+        let flat = Mat4d::tf_matrix(Vec3d::ZERO, Quatd::IDENT, Vec3d(1.0, 0.0, 1.0));
+        assert_eq!(Transform3Dd::from_mat4(&flat), None);
+    }
+
+    #[test]
+    fn transform3d_inverse_and_composition() {
+        let uniform = Transform3Dd::new(Vec3d(1.0, -2.0, 3.0), Quatd::rotator(Vec3d(0.3, -1.2, 0.7)), Vec3d::one(2.0));
+        let point = Vec3d(0.5, -2.0, 3.0);
+        assert_vec_eq(uniform.inverse().transform_point(uniform.transform_point(point)), point);
+        assert_vec_eq((uniform * uniform.inverse()).transform_point(point), point);
+
+        for a in transforms3d() {
+            for b in transforms3d() {
+                // composition is exact unless `a` has a non-uniform scale and `b` a rotation
+                if a.scale != Vec3d::ONE && b.rotation != Quatd::IDENT {
+                    continue;
+                }
+                assert_vec_eq((a * b).transform_point(point), a.transform_point(b.transform_point(point)));
+            }
+        }
+    }
+
+    #[test]
+    fn transform3d_lerp_endpoints() {
+        let all = transforms3d();
+        let (a, b) = (all[1], all[2]);
+        assert_vec_eq(a.lerp(&b, 0.0).transform_point(Vec3d::ONE), a.transform_point(Vec3d::ONE));
+        assert_vec_eq(a.lerp(&b, 1.0).transform_point(Vec3d::ONE), b.transform_point(Vec3d::ONE));
+        assert_vec_eq(a.lerp(&b, 0.5).translation, (a.translation + b.translation) * 0.5);
+    }
+
+    fn transforms2d() -> Vec<Transform2Dd> {
+        return vec![
+            Transform2Dd::IDENT,
+            Transform2Dd::new(Vec2d(1.0, -2.0), 0.7, Vec2d::ONE),
+            Transform2Dd::new(Vec2d(0.5, 4.0), -2.5, Vec2d(2.0, 0.5)),
+            Transform2Dd::new(Vec2d(-3.0, 1.0), 1.2, Vec2d(-1.5, 0.25)),
+        ];
+    }
+
+    #[test]
+    fn transform2d_matches_to_mat3() {
+        for tf in transforms2d() {
+            let mat = tf.to_mat3();
+            let point = Vec2d(0.5, -2.0);
+            assert_vec_eq(tf.transform_point(point), mat3_point(&mat, point));
+            assert_vec_eq(tf.inverse_transform_point(tf.transform_point(point)), point);
+        }
+
+        // rotations are counter-clockwise
+        let quarter = Transform2Dd::new(Vec2d::ZERO, std::f64::consts::FRAC_PI_2, Vec2d::ONE);
+        assert_vec_eq(quarter.transform_point(Vec2d::RIGHT), Vec2d::UP);
+    }
+
+    #[test]
+    fn transform2d_mat3_round_trip() {
+        for tf in transforms2d() {
+            let back = Transform2Dd::from_mat3(&tf.to_mat3()).unwrap();
+            for point in [Vec2d::ZERO, Vec2d::RIGHT, Vec2d::UP, Vec2d(0.5, -2.0)] {
+                assert_vec_eq(back.transform_point(point), tf.transform_point(point));
+            }
+        }
+
+        let mut sheared = Mat3d::tf_matrix(Vec2d(1.0, 2.0), 0.0, Vec2d::ONE);
+        sheared[(0, 1)] = 0.5;
+        assert_eq!(Transform2Dd::from_mat3(&sheared), None);
+    }
+
+    #[test]
+    fn transform2d_inverse_and_lerp() {
+        let uniform = Transform2Dd::new(Vec2d(1.0, -2.0), 0.7, Vec2d::one(3.0));
+        let point = Vec2d(0.5, -2.0);
+        assert_vec_eq(uniform.inverse().transform_point(uniform.transform_point(point)), point);
+
+        // the rotation takes the short way around
+        let a = Transform2Dd::new(Vec2d::ZERO, 3.0, Vec2d::ONE);
+        let b = Transform2Dd::new(Vec2d(2.0, 0.0), -3.0, Vec2d::ONE);
+        let half = a.lerp(&b, 0.5);
+        assert!((half.rot - std::f64::consts::PI).abs() < 1e-9, "{}", half.rot);
+        assert_vec_eq(half.pos, Vec2d(1.0, 0.0));
+    }
+
+    #[test]
+    fn transform2d_to_affine_matches_to_mat3() {
+        for tf in transforms2d() {
+            let mat = tf.to_mat3();
+            let affine = tf.to_affine();
+            for col in 0..3 {
+                for row in 0..2 {
+                    assert!((affine[col][row] - mat[(row, col)]).abs() < 1e-9);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// The SIMD types passed to the vector macros are only used by the `simd` backend,
+// the scalar backend ignores them.
+#[cfg(feature = "simd")]
+use std::simd::prelude::*;
+
+// Vectors
+crate::gen_vec2!(Vec2f, f32, 0.0, 1.0);
+crate::unsigned_vec2_impl!(Vec2f, f32, 0.0, 1.0);
+crate::signed_vec2_impl!(Vec2f, f32, 0.0, 1.0);
+crate::scalar_vec2_impl!(Vec2f, f32, f32x2);
+crate::float_vec2_impl!(Vec2f, f32, f32x2);
+crate::cast_vec2_impl!(Vec2f, f32, Vec2d, Vec2i, Vec2u);
+
+crate::gen_vec2!(Vec2d, f64, 0.0, 1.0);
+crate::unsigned_vec2_impl!(Vec2d, f64, 0.0, 1.0);
+crate::signed_vec2_impl!(Vec2d, f64, 0.0, 1.0);
+crate::scalar_vec2_impl!(Vec2d, f64, f64x2);
+crate::float_vec2_impl!(Vec2d, f64, f64x2);
+crate::cast_vec2_impl!(Vec2d, f64, Vec2f, Vec2i, Vec2u);
+
+crate::gen_vec2!(Vec2i, i32, 0, 1);
+crate::unsigned_vec2_impl!(Vec2i, i32, 0, 1);
+crate::signed_vec2_impl!(Vec2i, i32, 0, 1);
+crate::scalar_vec2_impl!(Vec2i, i32, i32x2);
+crate::cast_vec2_impl!(Vec2i, i32, Vec2f, Vec2d, Vec2u);
+
+crate::gen_vec2!(Vec2u, u32, 0, 1);
+crate::unsigned_vec2_impl!(Vec2u, u32, 0, 1);
+crate::scalar_vec2_impl!(Vec2u, u32, u32x2);
+crate::cast_vec2_impl!(Vec2u, u32, Vec2f, Vec2d, Vec2i);
+
+
+crate::gen_vec3!(Vec3f, Vec2f, f32, 0.0);
+crate::unsigned_vec3_impl!(Vec3f, f32, 0.0, 1.0);
+crate::signed_vec3_impl!(Vec3f, f32, 0.0, 1.0);
+crate::scalar_vec3_impl!(Vec3f, f32, f32x4, 0.0, f32::NEG_INFINITY, f32::INFINITY);
+crate::float_vec3_impl!(Vec3f, f32, f32x4);
+crate::rot_vec3_impl!(Vec3f, Quatf);
+crate::cast_vec3_impl!(Vec3f, f32, Vec3d, Vec3i, Vec3u);
+crate::updim_vec3_impl!(2, Vec3f, f32, Vec2f);
+
+crate::gen_vec3!(Vec3d, Vec2d, f64, 0.0);
+crate::unsigned_vec3_impl!(Vec3d, f64, 0.0, 1.0);
+crate::signed_vec3_impl!(Vec3d, f64, 0.0, 1.0);
+crate::scalar_vec3_impl!(Vec3d, f64, f64x4, 0.0, f64::NEG_INFINITY, f64::INFINITY);
+crate::float_vec3_impl!(Vec3d, f64, f64x4);
+crate::rot_vec3_impl!(Vec3d, Quatd);
+crate::cast_vec3_impl!(Vec3d, f64, Vec3f, Vec3i, Vec3u);
+crate::updim_vec3_impl!(2, Vec3d, f64, Vec2d);
+
+crate::gen_vec3!(Vec3i, Vec2i, i32, 0);
+crate::unsigned_vec3_impl!(Vec3i, i32, 0, 1);
+crate::signed_vec3_impl!(Vec3i, i32, 0, 1);
+crate::scalar_vec3_impl!(Vec3i, i32, i32x4, 0, i32::MIN, i32::MAX);
+crate::cast_vec3_impl!(Vec3i, i32, Vec3f, Vec3d, Vec3u);
+crate::updim_vec3_impl!(2, Vec3i, i32, Vec2i);
+
+crate::gen_vec3!(Vec3u, Vec2u, u32, 0);
+crate::unsigned_vec3_impl!(Vec3u, u32, 0, 1);
+crate::scalar_vec3_impl!(Vec3u, u32, u32x4, 0, u32::MIN, u32::MAX);
+crate::cast_vec3_impl!(Vec3u, u32, Vec3f, Vec3d, Vec3i);
+crate::updim_vec3_impl!(2, Vec3u, u32, Vec2u);
+
+
+crate::gen_vec4!(Vec4f, Vec3f, Vec2f, f32, 0.0);
+crate::unsigned_vec4_impl!(Vec4f, f32, 0.0, 1.0);
+crate::signed_vec4_impl!(Vec4f, f32, 0.0, 1.0);
+crate::scalar_vec4_impl!(Vec4f, f32, f32x4);
+crate::float_vec4_impl!(Vec4f, f32, f32x4);
+crate::cast_vec4_impl!(Vec4f, f32, Vec4d, Vec4i, Vec4u);
+crate::updim_vec4_impl!(3, Vec4f, f32, Vec3f);
+crate::updim_vec4_impl!(2, Vec4f, f32, Vec2f);
+
+crate::gen_vec4!(Vec4d, Vec3d, Vec2d, f64, 0.0);
+crate::unsigned_vec4_impl!(Vec4d, f64, 0.0, 1.0);
+crate::signed_vec4_impl!(Vec4d, f64, 0.0, 1.0);
+crate::scalar_vec4_impl!(Vec4d, f64, f64x4);
+crate::float_vec4_impl!(Vec4d, f64, f64x4);
+crate::cast_vec4_impl!(Vec4d, f64, Vec4f, Vec4i, Vec4u);
+crate::updim_vec4_impl!(3, Vec4d, f64, Vec3d);
+crate::updim_vec4_impl!(2, Vec4d, f64, Vec2d);
+
+crate::gen_vec4!(Vec4i, Vec3i, Vec2i, i32, 0);
+crate::unsigned_vec4_impl!(Vec4i, i32, 0, 1);
+crate::signed_vec4_impl!(Vec4i, i32, 0, 1);
+crate::scalar_vec4_impl!(Vec4i, i32, i32x4);
+crate::cast_vec4_impl!(Vec4i, i32, Vec4f, Vec4d, Vec4u);
+crate::updim_vec4_impl!(3, Vec4i, i32, Vec3i);
+crate::updim_vec4_impl!(2, Vec4i, i32, Vec2i);
+
+crate::gen_vec4!(Vec4u, Vec3u, Vec2u, u32, 0);
+crate::unsigned_vec4_impl!(Vec4u, u32, 0, 1);
+crate::scalar_vec4_impl!(Vec4u, u32, u32x4);
+crate::cast_vec4_impl!(Vec4u, u32, Vec4f, Vec4d, Vec4i);
+crate::updim_vec4_impl!(3, Vec4u, u32, Vec3u);
+crate::updim_vec4_impl!(2, Vec4u, u32, Vec2u);
+
+// Quaternions
+crate::quat_gen!(Quatf, Vec3f, f32);
+crate::quat_gen!(Quatd, Vec3d, f64);
+
+crate::dual_quat_gen!(DualQuatf, Quatf, Vec3f, Mat4f, f32);
+crate::dual_quat_gen!(DualQuatd, Quatd, Vec3d, Mat4d, f64);
+
+// Rects and boxes
+crate::gen_rect!(Rectf, Vec2f, f32, 2.0);
+crate::float_rect_impl!(Rectf, Vec2f);
+
+crate::gen_rect!(Rectd, Vec2d, f64, 2.0);
+crate::float_rect_impl!(Rectd, Vec2d);
+
+crate::gen_rect!(Recti, Vec2i, i32, 2);
+crate::int_rect_impl!(Recti, Vec2i, i32);
+
+crate::gen_rect!(Rectu, Vec2u, u32, 2);
+crate::int_rect_impl!(Rectu, Vec2u, u32);
+
+crate::gen_aabb!(Aabbf, Vec3f, f32, 2.0);
+crate::gen_aabb!(Aabbd, Vec3d, f64, 2.0);
+crate::gen_aabb!(Aabbi, Vec3i, i32, 2);
+
+// Geometric primitives
+crate::gen_ray2!(Ray2f, RayHit2f, Vec2f, Rectf, f32);
+crate::gen_ray2!(Ray2d, RayHit2d, Vec2d, Rectd, f64);
+crate::gen_ray3!(Ray3f, RayHit3f, Vec3f, f32);
+crate::gen_ray3!(Ray3d, RayHit3d, Vec3d, f64);
+
+crate::gen_plane!(Planef, Vec3f, f32);
+crate::gen_plane!(Planed, Vec3d, f64);
+crate::gen_frustum!(Frustumf, Planef, Vec3f, Quatf, Mat4f, Aabbf, f32);
+crate::gen_frustum!(Frustumd, Planed, Vec3d, Quatd, Mat4d, Aabbd, f64);
+
+crate::gen_circle!(Circlef, Vec2f, Mat3f, f32);
+crate::gen_circle!(Circled, Vec2d, Mat3d, f64);
+crate::gen_sphere!(Spheref, Vec3f, Mat4f, f32);
+crate::gen_sphere!(Sphered, Vec3d, Mat4d, f64);
+
+// Matrices
+crate::gen_mat2x2!(Mat2f, Vec2f, f32);
+crate::gen_mat2x2!(Mat2d, Vec2d, f64);
+
+crate::gen_mat3x3!(Mat3f, Vec3f, f32);
+crate::impl_tf3x3!(Mat3f, Vec2f, f32);
+crate::impl_rot3x3!(Mat3f, Quatf, f32);
+crate::impl_rect3x3!(Mat3f, Rectf);
+crate::gen_mat3x3!(Mat3d, Vec3d, f64);
+crate::impl_tf3x3!(Mat3d, Vec2d, f64);
+crate::impl_rot3x3!(Mat3d, Quatd, f64);
+crate::impl_rect3x3!(Mat3d, Rectd);
+
+crate::gen_mat4x4!(Mat4f, Vec4f, f32);
+crate::impl_tf4x4!(Mat4f, Vec3f, Quatf, f32);
+crate::impl_screen4x4!(Mat4f, Vec2f, Vec3f, Vec4f, Rectf, Ray3f, f32);
+crate::gen_mat4x4!(Mat4d, Vec4d, f64);
+crate::impl_tf4x4!(Mat4d, Vec3d, Quatd, f64);
+crate::impl_screen4x4!(Mat4d, Vec2d, Vec3d, Vec4d, Rectd, Ray3d, f64);
+
+// Transforms
+crate::gen_transform3d!(Transform3Df, Vec3f, Quatf, Mat4f, f32);
+crate::gen_transform3d!(Transform3Dd, Vec3d, Quatd, Mat4d, f64);
+crate::gen_transform2d!(Transform2Df, Vec2f, Mat3f, f32);
+crate::gen_transform2d!(Transform2Dd, Vec2d, Mat3d, f64);
+
+// Hierarchies
+pub type Hierarchy2Df = crate::hierarchy::Hierarchy<Transform2Df>;
+pub type Hierarchy2Dd = crate::hierarchy::Hierarchy<Transform2Dd>;
+pub type Hierarchy3Df = crate::hierarchy::Hierarchy<Transform3Df>;
+pub type Hierarchy3Dd = crate::hierarchy::Hierarchy<Transform3Dd>;
@@ -90,15 +90,67 @@ macro_rules! scalar_vec2_impl {
                 return Self(x * basis.0.0 + y * basis.1.0, x * basis.0.1 + y * basis.1.1)
             }
 
-            /// Performs the dot product between two vectors.
-            pub fn dot(self, other: Self) -> $typ {
-                return (self.to_simd() * other.to_simd()).reduce_sum();
-            }
-
             pub fn sqr_magnitude(self) -> $typ {
                 return self.dot(self);
             }
 
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                self.max(min).min(max)
+            }
+        }
+
+        impl std::ops::AddAssign for $ident {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl std::ops::SubAssign for $ident {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl std::ops::MulAssign<$typ> for $ident {
+            fn mul_assign(&mut self, rhs: $typ) {
+                *self = *self * rhs
+            }
+        }
+
+        impl std::ops::DivAssign<$typ> for $ident {
+            fn div_assign(&mut self, rhs: $typ) {
+                *self = *self / rhs
+            }
+        }
+        
+        impl From<($typ, $typ)> for $ident {
+            fn from(value: ($typ, $typ)) -> Self {
+                Self(value.0, value.1)
+            }
+        }
+        
+        impl From<$ident> for ($typ, $typ) {
+            fn from(value: $ident) -> Self {
+                (value.0, value.1)
+            }
+        }
+
+        $crate::vec2_backend_impl!($ident, $typ, $styp);
+        $crate::vector_trait_impl!($ident, $typ, 2);
+    };
+}
+
+/// Arithmetic of `scalar_vec2_impl` on `std::simd` vectors of type `$styp`. Only exists with the `simd` feature,
+/// and so do the `to_simd` and `from_simd` conversions it adds.
+#[cfg(feature = "simd")]
+#[macro_export]
+macro_rules! vec2_backend_impl {
+    ($ident:ident, $typ:ty, $styp:ty) => {
+        impl $ident {
+            /// Performs the dot product between two vectors.
+            pub fn dot(self, other: Self) -> $typ {
+                return (self.to_simd() * other.to_simd()).reduce_sum();
+            }
 
             /// Multiplies the vectors component-wise
             pub fn scale(self, other: Self) -> Self {
@@ -110,7 +162,6 @@ macro_rules! scalar_vec2_impl {
                 return Self::from_simd(self.to_simd() / other.to_simd());
             }
 
-
             pub fn min(self, other: Self) -> Self {
                 Self::from_simd(self.to_simd().simd_min(other.to_simd()))
             }
@@ -118,12 +169,7 @@ macro_rules! scalar_vec2_impl {
             pub fn max(self, other: Self) -> Self {
                 Self::from_simd(self.to_simd().simd_max(other.to_simd()))
             }
-        
-            pub fn clamp(self, min: Self, max: Self) -> Self {
-                self.max(min).min(max)
-            }
 
-            
             pub fn max_axis(self) -> $typ {
                 return self.to_simd().reduce_max();
             }
@@ -132,11 +178,12 @@ macro_rules! scalar_vec2_impl {
                 return self.to_simd().reduce_min();
             }
 
-
+            /// Only exists with the `simd` feature.
             pub fn to_simd(self) -> $styp {
                 <$styp>::from_array([self.0, self.1])
             }
         
+            /// Only exists with the `simd` feature.
             pub fn from_simd(simd: $styp) -> Self {
                 Self(simd[0], simd[1])
             }
@@ -150,12 +197,6 @@ macro_rules! scalar_vec2_impl {
             }
         }
         
-        impl std::ops::AddAssign for $ident {
-            fn add_assign(&mut self, rhs: Self) {
-                *self = *self + rhs
-            }
-        }
-        
         impl std::ops::Sub for $ident {
             type Output = Self;
         
@@ -164,12 +205,6 @@ macro_rules! scalar_vec2_impl {
             }
         }
 
-        impl std::ops::SubAssign for $ident {
-            fn sub_assign(&mut self, rhs: Self) {
-                *self = *self - rhs
-            }
-        }
-
         impl std::ops::Mul<$typ> for $ident {
             type Output = Self;
         
@@ -177,12 +212,6 @@ macro_rules! scalar_vec2_impl {
                 return Self::from_simd(self.to_simd() * <$styp>::splat(rhs));
             }
         }
-
-        impl std::ops::MulAssign<$typ> for $ident {
-            fn mul_assign(&mut self, rhs: $typ) {
-                *self = *self * rhs
-            }
-        }
         
         impl std::ops::Div<$typ> for $ident {
             type Output = Self;
@@ -191,22 +220,77 @@ macro_rules! scalar_vec2_impl {
                 return Self::from_simd(self.to_simd() / <$styp>::splat(rhs));
             }
         }
+    };
+}
 
-        impl std::ops::DivAssign<$typ> for $ident {
-            fn div_assign(&mut self, rhs: $typ) {
-                *self = *self * rhs
+/// Arithmetic of `scalar_vec2_impl` one component at a time, used without the `simd` feature. `$styp` is
+/// ignored and there are no `to_simd`/`from_simd` conversions.
+#[cfg(not(feature = "simd"))]
+#[macro_export]
+macro_rules! vec2_backend_impl {
+    ($ident:ident, $typ:ty, $styp:ty) => {
+        impl $ident {
+            /// Performs the dot product between two vectors.
+            pub fn dot(self, other: Self) -> $typ {
+                return self.0 * other.0 + self.1 * other.1;
+            }
+
+            /// Multiplies the vectors component-wise
+            pub fn scale(self, other: Self) -> Self {
+                return Self(self.0 * other.0, self.1 * other.1);
+            }
+
+            /// Divides the vectors component-wise
+            pub fn inv_scale(self, other: Self) -> Self {
+                return Self(self.0 / other.0, self.1 / other.1);
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0), self.1.min(other.1))
+            }
+        
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0), self.1.max(other.1))
+            }
+
+            pub fn max_axis(self) -> $typ {
+                return self.0.max(self.1);
+            }
+        
+            pub fn min_axis(self) -> $typ {
+                return self.0.min(self.1);
             }
         }
+
+        impl std::ops::Add for $ident {
+            type Output = Self;
         
-        impl From<($typ, $typ)> for $ident {
-            fn from(value: ($typ, $typ)) -> Self {
-                Self(value.0, value.1)
+            fn add(self, rhs: Self) -> Self::Output {
+                return Self(self.0 + rhs.0, self.1 + rhs.1);
             }
         }
         
-        impl From<$ident> for ($typ, $typ) {
-            fn from(value: $ident) -> Self {
-                (value.0, value.1)
+        impl std::ops::Sub for $ident {
+            type Output = Self;
+        
+            fn sub(self, rhs: Self) -> Self::Output {
+                return Self(self.0 - rhs.0, self.1 - rhs.1);
+            }
+        }
+
+        impl std::ops::Mul<$typ> for $ident {
+            type Output = Self;
+        
+            fn mul(self, rhs: $typ) -> Self::Output {
+                return Self(self.0 * rhs, self.1 * rhs);
+            }
+        }
+        
+        impl std::ops::Div<$typ> for $ident {
+            type Output = Self;
+        
+            fn div(self, rhs: $typ) -> Self::Output {
+                return Self(self.0 / rhs, self.1 / rhs);
             }
         }
     };
@@ -220,15 +304,15 @@ macro_rules! float_vec2_impl {
                 return Self(self.1, -self.0);
             }
         
-            /// Rotates the vector by the rotation provided in radians.
+            /// Rotates the vector counter-clockwise by the rotation provided in radians.
             pub fn rotate(self, rot: $typ) -> Self {
-                return Self(self.0 * rot.cos() + self.1 * rot.sin(), self.0 * rot.sin() - self.1 * rot.cos());
+                let (sin, cos) = rot.sin_cos();
+                return Self(self.0 * cos - self.1 * sin, self.0 * sin + self.1 * cos);
             }
         
             /// Rotates the vector by the specified rotation, but in a way that matches up with visual rotations.
             pub fn rotate_cw(self, rot: $typ) -> Self {
-                let rotated = self.rotate(rot);
-                return Self(rotated.0, -rotated.1);
+                return self.rotate(-rot);
             }
         
         
@@ -238,7 +322,7 @@ macro_rules! float_vec2_impl {
                     return 0.0;
                 }
         
-                return (self.to_simd() * self.to_simd()).reduce_sum().sqrt();
+                return self.sqr_magnitude().sqrt();
             }
         
             /// Returns the vector with a magnitude of 1.
@@ -263,7 +347,7 @@ macro_rules! float_vec2_impl {
         
             /// Inverts each component
             pub fn inv_dims(self) -> Self {
-                return Self::from_simd(<$styp>::splat(1.0) / self.to_simd());
+                return Self::ONE.inv_scale(self);
             }
         
             pub fn average(slice: &[Self]) -> Self {
@@ -301,25 +385,27 @@ macro_rules! float_vec2_impl {
         
         
             pub fn floor(self) -> Self {
-                return Self::from_simd(self.to_simd().floor());
+                return Self(self.0.floor(), self.1.floor());
             }
         
             pub fn round(self) -> Self {
-                return Self::from_simd(self.to_simd().round());
+                return Self(self.0.round(), self.1.round());
             }
         
             pub fn ceil(self) -> Self {
-                return Self::from_simd(self.to_simd().ceil());
+                return Self(self.0.ceil(), self.1.ceil());
             }
         }
 
-        impl Neg for $ident {
+        impl std::ops::Neg for $ident {
             type Output = Self;
         
             fn neg(self) -> Self::Output {
-                return Self::from_simd(-self.to_simd());
+                return Self(-self.0, -self.1);
             }
         }
+
+        $crate::inner_space_trait_impl!($ident, $typ);
     };
 }
 
@@ -344,4 +430,61 @@ macro_rules! cast_vec2_impl {
             }
         }
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_helpers::assert_vec_eq;
+
+    // instantiated outside of the crate's `types` module, with none of the operator traits in scope
+    mod local {
+        #[cfg(feature = "simd")]
+        use std::simd::prelude::*;
+
+        crate::gen_vec2!(Vec2, f64, 0.0, 1.0);
+        crate::unsigned_vec2_impl!(Vec2, f64, 0.0, 1.0);
+        crate::signed_vec2_impl!(Vec2, f64, 0.0, 1.0);
+        crate::scalar_vec2_impl!(Vec2, f64, f64x2);
+        crate::float_vec2_impl!(Vec2, f64, f64x2);
+    }
+
+    #[test]
+    fn local_instantiation_negates() {
+        assert_eq!(-local::Vec2(1.0, -2.0), local::Vec2(-1.0, 2.0));
+    }
+
+    #[test]
+    fn div_assign_divides() {
+        let mut v = local::Vec2(2.0, 4.0);
+        v /= 2.0;
+        assert_eq!(v, local::Vec2(1.0, 2.0));
+    }
+
+    // runs on the scalar backend under the default features and on `std::simd` with `--features simd`
+    #[test]
+    fn backend_arithmetic() {
+        let a = local::Vec2(1.0, -2.0);
+        let b = local::Vec2(3.0, 4.0);
+        assert_eq!(a.dot(b), -5.0);
+        assert_eq!(a.scale(b), local::Vec2(3.0, -8.0));
+        assert_eq!(b.inv_scale(local::Vec2(2.0, 4.0)), local::Vec2(1.5, 1.0));
+        assert_eq!(a.min(b), local::Vec2(1.0, -2.0));
+        assert_eq!(a.max(b), local::Vec2(3.0, 4.0));
+        assert_eq!(a.max_axis(), 1.0);
+        assert_eq!(a.min_axis(), -2.0);
+        assert_eq!(a + b, local::Vec2(4.0, 2.0));
+        assert_eq!(a - b, local::Vec2(-2.0, -6.0));
+        assert_eq!(a * 2.0, local::Vec2(2.0, -4.0));
+        assert_eq!(b / 2.0, local::Vec2(1.5, 2.0));
+    }
+
+    #[test]
+    fn rotate_is_counter_clockwise() {
+        let rot = std::f64::consts::FRAC_PI_2;
+        assert_vec_eq(Vec2d(1.0, 0.0).rotate(rot), Vec2d(0.0, 1.0));
+        assert_vec_eq(Vec2d(0.0, 1.0).rotate(rot), Vec2d(-1.0, 0.0));
+        assert_vec_eq(Vec2d(1.0, 0.0).rotate_cw(rot), Vec2d(0.0, -1.0));
+        assert_vec_eq(Vec2d(2.0, 1.0).rotate(0.4).rotate_cw(0.4), Vec2d(2.0, 1.0));
+    }
+}
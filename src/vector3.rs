@@ -120,15 +120,67 @@ macro_rules! signed_vec3_impl {
 macro_rules! scalar_vec3_impl {
     ($ident:ident, $typ:ty, $styp:ty, $zero:literal, $min:expr, $max:expr) => {
         impl $ident {
-            /// Performs the dot product between two vectors.
-            pub fn dot(self, other: Self) -> $typ {
-                return (self.to_simd() * other.to_simd()).reduce_sum();
-            }
-
             pub fn sqr_magnitude(self) -> $typ {
                 return self.dot(self);
             }
 
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                self.max(min).min(max)
+            }
+        }
+
+        impl std::ops::AddAssign for $ident {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl std::ops::SubAssign for $ident {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl std::ops::MulAssign<$typ> for $ident {
+            fn mul_assign(&mut self, rhs: $typ) {
+                *self = *self * rhs
+            }
+        }
+
+        impl std::ops::DivAssign<$typ> for $ident {
+            fn div_assign(&mut self, rhs: $typ) {
+                *self = *self / rhs
+            }
+        }
+        
+        impl From<($typ, $typ, $typ)> for $ident {
+            fn from(value: ($typ, $typ, $typ)) -> Self {
+                Self(value.0, value.1, value.2)
+            }
+        }
+        
+        impl From<$ident> for ($typ, $typ, $typ) {
+            fn from(value: $ident) -> Self {
+                (value.0, value.1, value.2)
+            }
+        }
+
+        $crate::vec3_backend_impl!($ident, $typ, $styp, $zero, $min, $max);
+        $crate::vector_trait_impl!($ident, $typ, 3);
+    };
+}
+
+/// Arithmetic of `scalar_vec3_impl` on `std::simd` vectors of type `$styp`. Only exists with the `simd` feature,
+/// and so do the `to_simd` and `from_simd` conversions it adds.
+#[cfg(feature = "simd")]
+#[macro_export]
+macro_rules! vec3_backend_impl {
+    ($ident:ident, $typ:ty, $styp:ty, $zero:literal, $min:expr, $max:expr) => {
+        impl $ident {
+            /// Performs the dot product between two vectors.
+            pub fn dot(self, other: Self) -> $typ {
+                return (self.to_simd() * other.to_simd()).reduce_sum();
+            }
 
             /// Multiplies the vectors component-wise
             pub fn scale(self, other: Self) -> Self {
@@ -140,7 +192,6 @@ macro_rules! scalar_vec3_impl {
                 return Self::from_simd(self.to_simd() / other.to_simd());
             }
 
-
             pub fn min(self, other: Self) -> Self {
                 Self::from_simd(self.to_simd().simd_min(other.to_simd()))
             }
@@ -148,12 +199,7 @@ macro_rules! scalar_vec3_impl {
             pub fn max(self, other: Self) -> Self {
                 Self::from_simd(self.to_simd().simd_max(other.to_simd()))
             }
-        
-            pub fn clamp(self, min: Self, max: Self) -> Self {
-                self.max(min).min(max)
-            }
 
-            
             pub fn max_axis(self) -> $typ {
                 return self.to_simd_ext($min).reduce_max();
             }
@@ -162,15 +208,17 @@ macro_rules! scalar_vec3_impl {
                 return self.to_simd_ext($max).reduce_min();
             }
 
-
+            /// Only exists with the `simd` feature. The unused fourth lane is zero.
             pub fn to_simd(self) -> $styp {
                 <$styp>::from_array([self.0, self.1, self.2, $zero])
             }
 
+            /// Only exists with the `simd` feature. The unused fourth lane is `w`.
             pub fn to_simd_ext(self, w: $typ) -> $styp {
                 <$styp>::from_array([self.0, self.1, self.2, w])
             }
         
+            /// Only exists with the `simd` feature.
             pub fn from_simd(simd: $styp) -> Self {
                 Self(simd[0], simd[1], simd[2])
             }
@@ -184,12 +232,6 @@ macro_rules! scalar_vec3_impl {
             }
         }
         
-        impl std::ops::AddAssign for $ident {
-            fn add_assign(&mut self, rhs: Self) {
-                *self = *self + rhs
-            }
-        }
-        
         impl std::ops::Sub for $ident {
             type Output = Self;
         
@@ -198,12 +240,6 @@ macro_rules! scalar_vec3_impl {
             }
         }
 
-        impl std::ops::SubAssign for $ident {
-            fn sub_assign(&mut self, rhs: Self) {
-                *self = *self - rhs
-            }
-        }
-
         impl std::ops::Mul<$typ> for $ident {
             type Output = Self;
         
@@ -211,12 +247,6 @@ macro_rules! scalar_vec3_impl {
                 return Self::from_simd(self.to_simd() * <$styp>::splat(rhs));
             }
         }
-
-        impl std::ops::MulAssign<$typ> for $ident {
-            fn mul_assign(&mut self, rhs: $typ) {
-                *self = *self * rhs
-            }
-        }
         
         impl std::ops::Div<$typ> for $ident {
             type Output = Self;
@@ -225,22 +255,77 @@ macro_rules! scalar_vec3_impl {
                 return Self::from_simd(self.to_simd() / <$styp>::splat(rhs));
             }
         }
+    };
+}
 
-        impl std::ops::DivAssign<$typ> for $ident {
-            fn div_assign(&mut self, rhs: $typ) {
-                *self = *self * rhs
+/// Arithmetic of `scalar_vec3_impl` one component at a time, used without the `simd` feature. `$styp` is
+/// ignored and there are no `to_simd`/`from_simd` conversions.
+#[cfg(not(feature = "simd"))]
+#[macro_export]
+macro_rules! vec3_backend_impl {
+    ($ident:ident, $typ:ty, $styp:ty, $zero:literal, $min:expr, $max:expr) => {
+        impl $ident {
+            /// Performs the dot product between two vectors.
+            pub fn dot(self, other: Self) -> $typ {
+                return self.0 * other.0 + self.1 * other.1 + self.2 * other.2;
+            }
+
+            /// Multiplies the vectors component-wise
+            pub fn scale(self, other: Self) -> Self {
+                return Self(self.0 * other.0, self.1 * other.1, self.2 * other.2);
+            }
+
+            /// Divides the vectors component-wise
+            pub fn inv_scale(self, other: Self) -> Self {
+                return Self(self.0 / other.0, self.1 / other.1, self.2 / other.2);
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0), self.1.min(other.1), self.2.min(other.2))
+            }
+        
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0), self.1.max(other.1), self.2.max(other.2))
+            }
+
+            pub fn max_axis(self) -> $typ {
+                return self.0.max(self.1).max(self.2);
+            }
+        
+            pub fn min_axis(self) -> $typ {
+                return self.0.min(self.1).min(self.2);
+            }
+        }
+
+        impl std::ops::Add for $ident {
+            type Output = Self;
+        
+            fn add(self, rhs: Self) -> Self::Output {
+                return Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2);
             }
         }
         
-        impl From<($typ, $typ, $typ)> for $ident {
-            fn from(value: ($typ, $typ, $typ)) -> Self {
-                Self(value.0, value.1, value.2)
+        impl std::ops::Sub for $ident {
+            type Output = Self;
+        
+            fn sub(self, rhs: Self) -> Self::Output {
+                return Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2);
             }
         }
+
+        impl std::ops::Mul<$typ> for $ident {
+            type Output = Self;
         
-        impl From<$ident> for ($typ, $typ, $typ) {
-            fn from(value: $ident) -> Self {
-                (value.0, value.1, value.2)
+            fn mul(self, rhs: $typ) -> Self::Output {
+                return Self(self.0 * rhs, self.1 * rhs, self.2 * rhs);
+            }
+        }
+        
+        impl std::ops::Div<$typ> for $ident {
+            type Output = Self;
+        
+            fn div(self, rhs: $typ) -> Self::Output {
+                return Self(self.0 / rhs, self.1 / rhs, self.2 / rhs);
             }
         }
     };
@@ -260,7 +345,7 @@ macro_rules! float_vec3_impl {
                     return 0.0;
                 }
         
-                return (self.to_simd() * self.to_simd()).reduce_sum().sqrt();
+                return self.sqr_magnitude().sqrt();
             }
         
             /// Returns the vector with a magnitude of 1.
@@ -285,7 +370,7 @@ macro_rules! float_vec3_impl {
         
             /// Inverts each component
             pub fn inv_dims(self) -> Self {
-                return Self::from_simd(<$styp>::splat(1.0) / self.to_simd());
+                return Self::ONE.inv_scale(self);
             }
         
             pub fn average(slice: &[Self]) -> Self {
@@ -323,15 +408,15 @@ macro_rules! float_vec3_impl {
         
         
             pub fn floor(self) -> Self {
-                return Self::from_simd(self.to_simd().floor());
+                return Self(self.0.floor(), self.1.floor(), self.2.floor());
             }
         
             pub fn round(self) -> Self {
-                return Self::from_simd(self.to_simd().round());
+                return Self(self.0.round(), self.1.round(), self.2.round());
             }
         
             pub fn ceil(self) -> Self {
-                return Self::from_simd(self.to_simd().ceil());
+                return Self(self.0.ceil(), self.1.ceil(), self.2.ceil());
             }
         }
 
@@ -339,9 +424,11 @@ macro_rules! float_vec3_impl {
             type Output = Self;
         
             fn neg(self) -> Self::Output {
-                return Self::from_simd(-self.to_simd());
+                return Self(-self.0, -self.1, -self.2);
             }
         }
+
+        $crate::inner_space_trait_impl!($ident, $typ);
     };
 }
 
@@ -357,6 +444,20 @@ macro_rules! rot_vec3_impl {
                 return (u * u.dot(self) * 2.0) + self * (s * s - u.sqr_magnitude()) + (u.cross(self) * 2.0 * s);
             }
         }
+
+        impl $crate::traits::Rotation for $quat {
+            type Vector = $ident;
+
+            const IDENTITY: Self = Self::IDENT;
+
+            fn rotate_vector(self, vec: $ident) -> $ident {
+                vec.rotate(self)
+            }
+
+            fn invert(self) -> Self {
+                self.inverted().conjugate()
+            }
+        }
     };
 }
 
@@ -400,4 +501,49 @@ macro_rules! updim_vec3_impl {
             }
         }
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    // instantiated outside of the crate's `types` module
+    mod local {
+        #[cfg(feature = "simd")]
+        use std::simd::prelude::*;
+
+        crate::gen_vec2!(Vec2, f64, 0.0, 1.0);
+        crate::gen_vec3!(Vec3, Vec2, f64, 0.0);
+        crate::unsigned_vec3_impl!(Vec3, f64, 0.0, 1.0);
+        crate::scalar_vec3_impl!(Vec3, f64, f64x4, 0.0, f64::NEG_INFINITY, f64::INFINITY);
+    }
+
+    #[test]
+    fn div_assign_divides() {
+        let mut v = local::Vec3(2.0, 4.0, 6.0);
+        v /= 2.0;
+        assert_eq!(v, local::Vec3(1.0, 2.0, 3.0));
+    }
+
+    // runs on the scalar backend under the default features and on `std::simd` with `--features simd`
+    #[test]
+    fn backend_arithmetic() {
+        let a = local::Vec3(1.0, -2.0, 5.0);
+        let b = local::Vec3(3.0, 4.0, -1.0);
+        assert_eq!(a.dot(b), -10.0);
+        assert_eq!(a.scale(b), local::Vec3(3.0, -8.0, -5.0));
+        assert_eq!(b.inv_scale(local::Vec3(2.0, 4.0, -1.0)), local::Vec3(1.5, 1.0, 1.0));
+        assert_eq!(a.min(b), local::Vec3(1.0, -2.0, -1.0));
+        assert_eq!(a.max(b), local::Vec3(3.0, 4.0, 5.0));
+        assert_eq!(a.max_axis(), 5.0);
+        assert_eq!(a.min_axis(), -2.0);
+        assert_eq!(a + b, local::Vec3(4.0, 2.0, 4.0));
+        assert_eq!(a - b, local::Vec3(-2.0, -6.0, 6.0));
+        assert_eq!(a * 2.0, local::Vec3(2.0, -4.0, 10.0));
+        assert_eq!(b / 2.0, local::Vec3(1.5, 2.0, -0.5));
+    }
+
+    #[test]
+    fn axes_ignore_the_padding_lane() {
+        assert_eq!(local::Vec3(-1.0, -2.0, -3.0).max_axis(), -1.0);
+        assert_eq!(local::Vec3(1.0, 2.0, 3.0).min_axis(), 1.0);
+    }
+}
@@ -169,15 +169,67 @@ macro_rules! signed_vec4_impl {
 macro_rules! scalar_vec4_impl {
     ($ident:ident, $typ:ty, $styp:ty) => {
         impl $ident {
-            /// Performs the dot product between two vectors.
-            pub fn dot(self, other: Self) -> $typ {
-                return (self.to_simd() * other.to_simd()).reduce_sum();
-            }
-
             pub fn sqr_magnitude(self) -> $typ {
                 return self.dot(self);
             }
 
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                self.max(min).min(max)
+            }
+        }
+
+        impl std::ops::AddAssign for $ident {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl std::ops::SubAssign for $ident {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl std::ops::MulAssign<$typ> for $ident {
+            fn mul_assign(&mut self, rhs: $typ) {
+                *self = *self * rhs
+            }
+        }
+
+        impl std::ops::DivAssign<$typ> for $ident {
+            fn div_assign(&mut self, rhs: $typ) {
+                *self = *self / rhs
+            }
+        }
+        
+        impl From<($typ, $typ, $typ, $typ)> for $ident {
+            fn from(value: ($typ, $typ, $typ, $typ)) -> Self {
+                Self(value.0, value.1, value.2, value.3)
+            }
+        }
+        
+        impl From<$ident> for ($typ, $typ, $typ, $typ) {
+            fn from(value: $ident) -> Self {
+                (value.0, value.1, value.2, value.3)
+            }
+        }
+
+        $crate::vec4_backend_impl!($ident, $typ, $styp);
+        $crate::vector_trait_impl!($ident, $typ, 4);
+    };
+}
+
+/// Arithmetic of `scalar_vec4_impl` on `std::simd` vectors of type `$styp`. Only exists with the `simd` feature,
+/// and so do the `to_simd` and `from_simd` conversions it adds.
+#[cfg(feature = "simd")]
+#[macro_export]
+macro_rules! vec4_backend_impl {
+    ($ident:ident, $typ:ty, $styp:ty) => {
+        impl $ident {
+            /// Performs the dot product between two vectors.
+            pub fn dot(self, other: Self) -> $typ {
+                return (self.to_simd() * other.to_simd()).reduce_sum();
+            }
 
             /// Multiplies the vectors component-wise
             pub fn scale(self, other: Self) -> Self {
@@ -189,7 +241,6 @@ macro_rules! scalar_vec4_impl {
                 return Self::from_simd(self.to_simd() / other.to_simd());
             }
 
-
             pub fn min(self, other: Self) -> Self {
                 Self::from_simd(self.to_simd().simd_min(other.to_simd()))
             }
@@ -197,12 +248,7 @@ macro_rules! scalar_vec4_impl {
             pub fn max(self, other: Self) -> Self {
                 Self::from_simd(self.to_simd().simd_max(other.to_simd()))
             }
-        
-            pub fn clamp(self, min: Self, max: Self) -> Self {
-                self.max(min).min(max)
-            }
 
-            
             pub fn max_axis(self) -> $typ {
                 return self.to_simd().reduce_max();
             }
@@ -211,11 +257,12 @@ macro_rules! scalar_vec4_impl {
                 return self.to_simd().reduce_min();
             }
 
-
+            /// Only exists with the `simd` feature.
             pub fn to_simd(self) -> $styp {
                 <$styp>::from_array([self.0, self.1, self.2, self.3])
             }
         
+            /// Only exists with the `simd` feature.
             pub fn from_simd(simd: $styp) -> Self {
                 Self(simd[0], simd[1], simd[2], simd[3])
             }
@@ -229,12 +276,6 @@ macro_rules! scalar_vec4_impl {
             }
         }
         
-        impl std::ops::AddAssign for $ident {
-            fn add_assign(&mut self, rhs: Self) {
-                *self = *self + rhs
-            }
-        }
-        
         impl std::ops::Sub for $ident {
             type Output = Self;
         
@@ -243,12 +284,6 @@ macro_rules! scalar_vec4_impl {
             }
         }
 
-        impl std::ops::SubAssign for $ident {
-            fn sub_assign(&mut self, rhs: Self) {
-                *self = *self - rhs
-            }
-        }
-
         impl std::ops::Mul<$typ> for $ident {
             type Output = Self;
         
@@ -256,12 +291,6 @@ macro_rules! scalar_vec4_impl {
                 return Self::from_simd(self.to_simd() * <$styp>::splat(rhs));
             }
         }
-
-        impl std::ops::MulAssign<$typ> for $ident {
-            fn mul_assign(&mut self, rhs: $typ) {
-                *self = *self * rhs
-            }
-        }
         
         impl std::ops::Div<$typ> for $ident {
             type Output = Self;
@@ -270,22 +299,77 @@ macro_rules! scalar_vec4_impl {
                 return Self::from_simd(self.to_simd() / <$styp>::splat(rhs));
             }
         }
+    };
+}
 
-        impl std::ops::DivAssign<$typ> for $ident {
-            fn div_assign(&mut self, rhs: $typ) {
-                *self = *self * rhs
+/// Arithmetic of `scalar_vec4_impl` one component at a time, used without the `simd` feature. `$styp` is
+/// ignored and there are no `to_simd`/`from_simd` conversions.
+#[cfg(not(feature = "simd"))]
+#[macro_export]
+macro_rules! vec4_backend_impl {
+    ($ident:ident, $typ:ty, $styp:ty) => {
+        impl $ident {
+            /// Performs the dot product between two vectors.
+            pub fn dot(self, other: Self) -> $typ {
+                return self.0 * other.0 + self.1 * other.1 + self.2 * other.2 + self.3 * other.3;
+            }
+
+            /// Multiplies the vectors component-wise
+            pub fn scale(self, other: Self) -> Self {
+                return Self(self.0 * other.0, self.1 * other.1, self.2 * other.2, self.3 * other.3);
+            }
+
+            /// Divides the vectors component-wise
+            pub fn inv_scale(self, other: Self) -> Self {
+                return Self(self.0 / other.0, self.1 / other.1, self.2 / other.2, self.3 / other.3);
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0), self.1.min(other.1), self.2.min(other.2), self.3.min(other.3))
+            }
+        
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0), self.1.max(other.1), self.2.max(other.2), self.3.max(other.3))
+            }
+
+            pub fn max_axis(self) -> $typ {
+                return self.0.max(self.1).max(self.2).max(self.3);
+            }
+        
+            pub fn min_axis(self) -> $typ {
+                return self.0.min(self.1).min(self.2).min(self.3);
             }
         }
+
+        impl std::ops::Add for $ident {
+            type Output = Self;
         
-        impl From<($typ, $typ, $typ, $typ)> for $ident {
-            fn from(value: ($typ, $typ, $typ, $typ)) -> Self {
-                Self(value.0, value.1, value.2, value.3)
+            fn add(self, rhs: Self) -> Self::Output {
+                return Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2, self.3 + rhs.3);
             }
         }
         
-        impl From<$ident> for ($typ, $typ, $typ, $typ) {
-            fn from(value: $ident) -> Self {
-                (value.0, value.1, value.2, value.3)
+        impl std::ops::Sub for $ident {
+            type Output = Self;
+        
+            fn sub(self, rhs: Self) -> Self::Output {
+                return Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2, self.3 - rhs.3);
+            }
+        }
+
+        impl std::ops::Mul<$typ> for $ident {
+            type Output = Self;
+        
+            fn mul(self, rhs: $typ) -> Self::Output {
+                return Self(self.0 * rhs, self.1 * rhs, self.2 * rhs, self.3 * rhs);
+            }
+        }
+        
+        impl std::ops::Div<$typ> for $ident {
+            type Output = Self;
+        
+            fn div(self, rhs: $typ) -> Self::Output {
+                return Self(self.0 / rhs, self.1 / rhs, self.2 / rhs, self.3 / rhs);
             }
         }
     };
@@ -301,7 +385,7 @@ macro_rules! float_vec4_impl {
                     return 0.0;
                 }
         
-                return (self.to_simd() * self.to_simd()).reduce_sum().sqrt();
+                return self.sqr_magnitude().sqrt();
             }
         
             /// Returns the vector with a magnitude of 1.
@@ -326,7 +410,7 @@ macro_rules! float_vec4_impl {
         
             /// Inverts each component
             pub fn inv_dims(self) -> Self {
-                return Self::from_simd(<$styp>::splat(1.0) / self.to_simd());
+                return Self::ONE.inv_scale(self);
             }
         
             pub fn average(slice: &[Self]) -> Self {
@@ -364,15 +448,15 @@ macro_rules! float_vec4_impl {
         
         
             pub fn floor(self) -> Self {
-                return Self::from_simd(self.to_simd().floor());
+                return Self(self.0.floor(), self.1.floor(), self.2.floor(), self.3.floor());
             }
         
             pub fn round(self) -> Self {
-                return Self::from_simd(self.to_simd().round());
+                return Self(self.0.round(), self.1.round(), self.2.round(), self.3.round());
             }
         
             pub fn ceil(self) -> Self {
-                return Self::from_simd(self.to_simd().ceil());
+                return Self(self.0.ceil(), self.1.ceil(), self.2.ceil(), self.3.ceil());
             }
         }
 
@@ -380,9 +464,11 @@ macro_rules! float_vec4_impl {
             type Output = Self;
         
             fn neg(self) -> Self::Output {
-                return Self::from_simd(-self.to_simd());
+                return Self(-self.0, -self.1, -self.2, -self.3);
             }
         }
+
+        $crate::inner_space_trait_impl!($ident, $typ);
     };
 }
 
@@ -458,4 +544,44 @@ macro_rules! updim_vec4_impl {
             }
         }
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    // instantiated outside of the crate's `types` module
+    mod local {
+        #[cfg(feature = "simd")]
+        use std::simd::prelude::*;
+
+        crate::gen_vec2!(Vec2, f64, 0.0, 1.0);
+        crate::gen_vec3!(Vec3, Vec2, f64, 0.0);
+        crate::gen_vec4!(Vec4, Vec3, Vec2, f64, 0.0);
+        crate::unsigned_vec4_impl!(Vec4, f64, 0.0, 1.0);
+        crate::scalar_vec4_impl!(Vec4, f64, f64x4);
+    }
+
+    #[test]
+    fn div_assign_divides() {
+        let mut v = local::Vec4(2.0, 4.0, 6.0, 8.0);
+        v /= 2.0;
+        assert_eq!(v, local::Vec4(1.0, 2.0, 3.0, 4.0));
+    }
+
+    // runs on the scalar backend under the default features and on `std::simd` with `--features simd`
+    #[test]
+    fn backend_arithmetic() {
+        let a = local::Vec4(1.0, -2.0, 5.0, 0.5);
+        let b = local::Vec4(3.0, 4.0, -1.0, 2.0);
+        assert_eq!(a.dot(b), -9.0);
+        assert_eq!(a.scale(b), local::Vec4(3.0, -8.0, -5.0, 1.0));
+        assert_eq!(b.inv_scale(local::Vec4(2.0, 4.0, -1.0, 4.0)), local::Vec4(1.5, 1.0, 1.0, 0.5));
+        assert_eq!(a.min(b), local::Vec4(1.0, -2.0, -1.0, 0.5));
+        assert_eq!(a.max(b), local::Vec4(3.0, 4.0, 5.0, 2.0));
+        assert_eq!(a.max_axis(), 5.0);
+        assert_eq!(a.min_axis(), -2.0);
+        assert_eq!(a + b, local::Vec4(4.0, 2.0, 4.0, 2.5));
+        assert_eq!(a - b, local::Vec4(-2.0, -6.0, 6.0, -1.5));
+        assert_eq!(a * 2.0, local::Vec4(2.0, -4.0, 10.0, 1.0));
+        assert_eq!(b / 2.0, local::Vec4(1.5, 2.0, -0.5, 1.0));
+    }
+}